@@ -39,11 +39,11 @@ static WORDS : Lazy<Vec<String>> = Lazy::new(|| {
 });
 
 
-const OVERLONG_EN_US : &'static str =
+const OVERLONG_EN_US : &str =
  "Lopadotemachoselachogaleokranioleipsanodrimhypotrimmatosilphiokarabomelitokatakechymenokichlepik\
   ossyphophattoperisteralektryonoptekephalliokigklopeleiolagoiosiraiobaphetraganopterygon";
 
-const OVERLONG_GRC : &'static str =
+const OVERLONG_GRC : &str =
  "λοπαδοτεμαχοσελαχογαλεοκρανιολειψανοδριμυποτριμματοσιλφιοκαραβομελιτοκατακεχυμενοκιχλεπικοσσυφοφ\
   αττοπεριστεραλεκτρυονοπτοκεφαλλιοκιγκλοπελειολαγῳοσιραιοβαφητραγανοπτερύγων";
 
@@ -1,8 +1,6 @@
 #![allow(dead_code)]
 
 #[cfg(feature = "embed_all")] extern crate pocket_resources;
-#[cfg(any(feature = "nfc", feature = "nfd", feature = "nfkc", feature = "nfkd"))]
-extern crate unicode_normalization;
 
 extern crate bincode;
 extern crate fst;
@@ -23,35 +21,39 @@ use std::path::{Path, PathBuf};
 
 use hyphenation_commons::dictionary::extended as ext;
 use hyphenation_commons::dictionary::{self, *};
-use hyphenation_commons::parse::Parse;
-use hyphenation_commons::Language::{self, *};
+use hyphenation_commons::header::{Header, Kind};
+use hyphenation_commons::parse::{self, Parse};
+use hyphenation_commons::Language;
 
 
-// Configuration of exclusive optional features
+// Configuration of optional features
 
 use configuration::*;
 mod configuration {
-    // In service of configurable normalization forms, a type alias and a function
-    // are defined via conditional compilation.
-    //
-    // If no feature is explicitly set, normalization is avoided altogether.
-
-    // Neither Cargo nor rustc allows us to set exclusive features; we must indulge
-    // them with this clumsy branle of cfg declarations.
-    #[cfg(not(any(feature = "nfc", feature = "nfd", feature = "nfkc", feature = "nfkd")))]
-    pub fn normalize(s : &str) -> String { s.to_owned() }
-
-    #[cfg(any(feature = "nfc", feature = "nfd", feature = "nfkc", feature = "nfkd"))]
-    use unicode_normalization::*;
-
-    #[cfg(feature = "nfc")]
-    pub fn normalize(s : &str) -> String { s.nfc().collect() }
-    #[cfg(feature = "nfd")]
-    pub fn normalize(s : &str) -> String { s.nfd().collect() }
-    #[cfg(feature = "nfkc")]
-    pub fn normalize(s : &str) -> String { s.nfkc().collect() }
-    #[cfg(feature = "nfkd")]
-    pub fn normalize(s : &str) -> String { s.nfkd().collect() }
+    use hyphenation_commons::{caseless, Language, Normalization};
+
+    // The normalization form of the bundled dictionaries, which is recorded in
+    // each of them. Should Cargo unify the features of several dependents, more
+    // than one form may be requested; any would serve, since words are
+    // normalized to the form of the dictionary at hand. The compatibility forms
+    // take precedence, and decomposition over composition.
+    pub fn normalization() -> Normalization {
+        if cfg!(feature = "nfkd") {
+            Normalization::Nfkd
+        } else if cfg!(feature = "nfkc") {
+            Normalization::Nfkc
+        } else if cfg!(feature = "nfd") {
+            Normalization::Nfd
+        } else {
+            Normalization::Nfc
+        }
+    }
+
+    // Patterns and exceptions are normalized, then case-folded as suits their
+    // language.
+    pub fn normalize(s : &str, language : Language) -> String {
+        caseless(s, normalization(), language.folding())
+    }
 }
 
 
@@ -128,11 +130,17 @@ trait Build: Sized + Parse + TryFromIterator<<Self as Parse>::Tally> {
     }
 
     fn build(lang : Language, paths : &Paths) -> Result<Self, Error> {
-        let file = File::open(Self::sourcepath(lang, paths))?;
-        let by_line = io::BufReader::new(file).lines();
-        let pairs = by_line.map(|res| Self::pair(&res.unwrap(), normalize));
+        let path = Self::sourcepath(lang, paths);
+        let file = File::open(&path)?;
+        let mut pairs = vec![];
+        for (n, line) in io::BufReader::new(file).lines().enumerate() {
+            let pair = Self::pair(&line?, |s| normalize(s, lang)).map_err(|e| {
+                                                                 e.in_file(&path).at_line(n + 1)
+                                                             })?;
+            pairs.push(pair);
+        }
 
-        Self::try_from_iter(pairs.into_iter())
+        Self::try_from_iter(pairs)
     }
 }
 
@@ -147,11 +155,13 @@ impl Build for ext::Patterns {
 }
 
 
-fn write<T>(item : &T, path : &Path) -> Result<(), Error>
+fn write<T>(item : &T, header : &Header, path : &Path) -> Result<(), Error>
     where T : ser::Serialize
 {
-    let mut buffer = File::create(&path).map(io::BufWriter::new)?;
-    bin::serialize_into(&mut buffer, item)?;
+    let body = bin::serialize(item)?;
+    let mut buffer = File::create(path).map(io::BufWriter::new)?;
+    header.write(&mut buffer, &body)?;
+    buffer.flush()?;
     Ok(())
 }
 
@@ -172,87 +182,10 @@ fn main() {
     let _dict_source = paths.source_item(dict_folder);
     let dict_out = paths.place_item(dict_folder);
 
-    let _ext_langs = vec![Catalan, Hungarian];
-    let _std_langs = vec![Afrikaans,
-                          Albanian,
-                          Armenian,
-                          Assamese,
-                          Basque,
-                          Belarusian,
-                          Bengali,
-                          Bulgarian,
-                          Catalan,
-                          Chinese,
-                          Coptic,
-                          Croatian,
-                          Czech,
-                          Danish,
-                          Dutch,
-                          EnglishGB,
-                          EnglishUS,
-                          Esperanto,
-                          Estonian,
-                          Ethiopic,
-                          Finnish,
-                          FinnishScholastic,
-                          French,
-                          Friulan,
-                          Galician,
-                          Georgian,
-                          German1901,
-                          German1996,
-                          GermanSwiss,
-                          GreekAncient,
-                          GreekMono,
-                          GreekPoly,
-                          Gujarati,
-                          Hindi,
-                          Hungarian,
-                          Icelandic,
-                          Indonesian,
-                          Interlingua,
-                          Irish,
-                          Italian,
-                          Kannada,
-                          Kurmanji,
-                          Latin,
-                          LatinClassic,
-                          LatinLiturgical,
-                          Latvian,
-                          Lithuanian,
-                          Macedonian,
-                          Malayalam,
-                          Marathi,
-                          Mongolian,
-                          NorwegianBokmal,
-                          NorwegianNynorsk,
-                          Occitan,
-                          Oriya,
-                          Pali,
-                          Panjabi,
-                          Piedmontese,
-                          Polish,
-                          Portuguese,
-                          Romanian,
-                          Romansh,
-                          Russian,
-                          Sanskrit,
-                          SerbianCyrillic,
-                          SerbocroatianCyrillic,
-                          SerbocroatianLatin,
-                          SlavonicChurch,
-                          Slovak,
-                          Slovenian,
-                          Spanish,
-                          Swedish,
-                          Tamil,
-                          Telugu,
-                          Thai,
-                          Turkish,
-                          Turkmen,
-                          Ukrainian,
-                          Uppersorbian,
-                          Welsh];
+    let _std_langs : Vec<Language> = Language::all().collect();
+    let _ext_langs : Vec<Language> =
+        Language::all().filter(|l| l.patterns(Kind::Extended).is_some())
+                       .collect();
 
     fs::create_dir_all(&dict_out).unwrap();
 
@@ -282,10 +215,13 @@ fn main() {
                 Builder { language,
                           patterns : Patterns::build(language, &paths).unwrap(),
                           exceptions:
-                              Exceptions::build(language, &paths).unwrap_or(Exceptions::default()) };
+                              Exceptions::build(language, &paths).unwrap_or(Exceptions::default()),
+                          normalization : normalization() };
 
             let dict = Standard::from(builder);
-            write(&dict, &paths.place_dict(language, _std_out)).unwrap();
+            let source = language.patterns(Kind::Standard).unwrap().version;
+            let header = Header::new(Kind::Standard, language, source);
+            write(&dict, &header, &paths.place_dict(language, _std_out)).unwrap();
         }
 
         println!("Building `Extended` dictionaries:");
@@ -294,10 +230,13 @@ fn main() {
             let builder = ext::Builder { language,
                                          patterns:
                                              ext::Patterns::build(language, &paths).unwrap(),
-                                         exceptions : ext::Exceptions::default() };
+                                         exceptions : ext::Exceptions::default(),
+                                         normalization : normalization() };
 
             let dict = ext::Extended::from(builder);
-            write(&dict, &paths.place_dict(language, _ext_out)).unwrap();
+            let source = language.patterns(Kind::Extended).unwrap().version;
+            let header = Header::new(Kind::Extended, language, source);
+            write(&dict, &header, &paths.place_dict(language, _ext_out)).unwrap();
         }
     }
 
@@ -305,7 +244,7 @@ fn main() {
     {
         use std::iter;
 
-        let dict = (&dict_folder, Paths::dict_name(EnglishUS, _std_out));
+        let dict = (&dict_folder, Paths::dict_name(Language::EnglishUS, _std_out));
         pocket_resources::package(iter::once(&dict)).unwrap();
     }
 
@@ -331,8 +270,9 @@ pub enum Error {
     Build(fst::Error),
     Env(env::VarError),
     IO(io::Error),
+    Parse(parse::Error),
     Serialization(bin::Error),
-    Resource, // TODO: Parsing
+    Resource,
 }
 
 impl error::Error for Error {
@@ -341,6 +281,7 @@ impl error::Error for Error {
             Error::Build(ref e) => Some(e),
             Error::Env(ref e) => Some(e),
             Error::IO(ref e) => Some(e),
+            Error::Parse(ref e) => Some(e),
             Error::Serialization(ref e) => Some(e),
             _ => None,
         }
@@ -353,6 +294,7 @@ impl fmt::Display for Error {
             Error::Build(ref e) => e.fmt(f),
             Error::Env(ref e) => e.fmt(f),
             Error::IO(ref e) => e.fmt(f),
+            Error::Parse(ref e) => e.fmt(f),
             Error::Serialization(ref e) => e.fmt(f),
             Error::Resource => f.write_str("dictionary could not be embedded"),
         }
@@ -371,6 +313,10 @@ impl From<bin::Error> for Error {
     fn from(err : bin::Error) -> Error { Error::Serialization(err) }
 }
 
+impl From<parse::Error> for Error {
+    fn from(err : parse::Error) -> Error { Error::Parse(err) }
+}
+
 impl From<fst::Error> for Error {
     fn from(err : fst::Error) -> Error { Error::Build(err) }
 }
@@ -7,8 +7,10 @@ Data structures for extended hyphenation[1].
 use std::collections::HashMap;
 
 use crate::dictionary::trie::{self, Trie};
-use crate::dictionary::{uniques, Locus, PrefixTallies};
+use crate::dictionary::{self, coalesce, uniques, Locus, PrefixTallies};
 use crate::language::Language;
+use crate::normalization::Normalization;
+use crate::options::HyphenationOptions;
 use crate::parse::Parse;
 
 /// The partial score carried by an extended hyphenation pattern.
@@ -37,17 +39,29 @@ pub struct Subregion {
 }
 
 /// A trie mapping hyphenation patterns to their extended tallies.
-#[derive(Debug, Default, Serialize, Deserialize)]
-pub struct Patterns {
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(bound(serialize = "", deserialize = "Trie<D>: serde::Deserialize<'de>"))]
+pub struct Patterns<D : AsRef<[u8]> = Vec<u8>> {
     tallies :   Vec<Tally>,
-    automaton : Trie,
+    automaton : Trie<D>,
+}
+
+impl Default for Patterns {
+    fn default() -> Self {
+        Patterns { tallies :   vec![],
+                   automaton : Trie::default(), }
+    }
 }
 
 impl Patterns {
     pub fn from_iter<I>(iter : I) -> Result<Self, trie::Error>
         where I : IntoIterator<Item = (String, <Patterns as Parse>::Tally)>
     {
-        let (kvs, tallies) = uniques(iter.into_iter());
+        let merge = |tally : &mut Tally, other : Tally| {
+            dictionary::merge(&mut tally.standard, other.standard);
+            tally.subregion = tally.subregion.take().or(other.subregion);
+        };
+        let (kvs, tallies) = uniques(coalesce(iter, merge).into_iter());
         let automaton = Trie::from_iter(kvs.into_iter())?;
         Ok(Patterns { tallies, automaton })
     }
@@ -63,24 +77,33 @@ pub struct Exceptions(pub HashMap<String, Vec<(usize, Option<Subregion>)>>);
 /// OpenOffice.org".
 ///
 /// It comprises the working language, the set of extended patterns and
-/// exceptions, and the character boundaries for hyphenation.
+/// exceptions, the options that limit hyphenation, and the normalization
+/// form of its patterns. As with
+/// `Standard`, the pattern trie may be borrowed from a buffer.
 #[derive(Debug, Serialize, Deserialize)]
-pub struct Extended {
+#[serde(bound(serialize = "", deserialize = "Patterns<D>: serde::Deserialize<'de>"))]
+pub struct Extended<D : AsRef<[u8]> = Vec<u8>> {
     language :       Language,
-    patterns :       Patterns,
+    patterns :       Patterns<D>,
     pub exceptions : Exceptions,
-    /// The minimum number of `char`s from the start and end of a word where
-    /// breaks may not occur.
-    pub minima :     (usize, usize),
+    /// The limits within which words are hyphenated, such as the minimum
+    /// number of graphemes from the start and end of a word where breaks may
+    /// not occur.
+    pub options :    HyphenationOptions,
+    normalization :  Normalization,
 }
 
-impl Extended {
+impl<D : AsRef<[u8]>> Extended<D> {
     /// The language for which this dictionary can provide hyphenation.
     pub fn language(&self) -> Language { self.language }
 
+    /// The normalization form of the patterns and exceptions, to which words
+    /// should be normalized before hyphenation.
+    pub fn normalization(&self) -> Normalization { self.normalization }
+
     /// An iterator over the tallies associated to all prefixes of the query,
     /// including the query itself.
-    pub fn prefix_tallies<'f, 'q>(&'f self, query : &'q [u8]) -> PrefixTallies<'f, 'q, Tally> {
+    pub fn prefix_tallies<'f, 'q>(&'f self, query : &'q [u8]) -> PrefixTallies<'f, 'q, Tally, D> {
         PrefixTallies { matches : self.patterns.automaton.get_prefixes(query),
                         tallies : &self.patterns.tallies, }
     }
@@ -90,16 +113,18 @@ impl Extended {
 /// hygiene.
 #[derive(Debug)]
 pub struct Builder {
-    pub language :   Language,
-    pub patterns :   Patterns,
-    pub exceptions : Exceptions,
+    pub language :      Language,
+    pub patterns :      Patterns,
+    pub exceptions :    Exceptions,
+    pub normalization : Normalization,
 }
 
 impl From<Builder> for Extended {
     fn from(b : Builder) -> Extended {
-        Extended { language :   b.language,
-                   patterns :   b.patterns,
-                   exceptions : b.exceptions,
-                   minima :     b.language.minima(), }
+        Extended { language :      b.language,
+                   patterns :      b.patterns,
+                   exceptions :    b.exceptions,
+                   options :       b.language.into(),
+                   normalization : b.normalization, }
     }
 }
@@ -3,12 +3,15 @@
 pub mod extended;
 mod trie;
 
-use std::collections::HashMap;
+use std::collections::btree_map::Entry;
+use std::collections::{BTreeMap, HashMap};
 use std::hash::Hash;
 
 use crate::dictionary::trie::PrefixMatches;
 pub use crate::dictionary::trie::{Error, Trie};
 use crate::language::Language;
+use crate::normalization::Normalization;
+use crate::options::HyphenationOptions;
 use crate::parse::Parse;
 
 
@@ -19,59 +22,119 @@ pub struct Locus {
 }
 
 /// A trie mapping hyphenation patterns to their tallies.
-#[derive(Clone, Debug, Default, Serialize, Deserialize)]
-pub struct Patterns {
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(bound(serialize = "", deserialize = "Trie<D>: serde::Deserialize<'de>"))]
+pub struct Patterns<D : AsRef<[u8]> = Vec<u8>> {
     tallies :   Vec<Vec<Locus>>,
-    automaton : Trie,
+    automaton : Trie<D>,
+}
+
+impl Default for Patterns {
+    fn default() -> Self {
+        Patterns { tallies :   vec![],
+                   automaton : Trie::default(), }
+    }
 }
 
 impl Patterns {
     pub fn from_iter<I>(iter : I) -> Result<Self, trie::Error>
         where I : IntoIterator<Item = (String, <Patterns as Parse>::Tally)>
     {
-        let (kvs, tallies) = uniques(iter.into_iter());
+        let (kvs, tallies) = uniques(coalesce(iter, merge).into_iter());
         let automaton = Trie::from_iter(kvs.into_iter())?;
         Ok(Patterns { tallies, automaton })
     }
 }
 
+/// Merge two tallies of a same pattern, keeping the greatest value at each
+/// index.
+pub fn merge(tally : &mut Vec<Locus>, other : Vec<Locus>) {
+    for locus in other {
+        match tally.iter_mut().find(|l| l.index == locus.index) {
+            Some(l) => l.value = l.value.max(locus.value),
+            None => tally.push(locus),
+        }
+    }
+    tally.sort_by_key(|l| l.index);
+}
+
+/// Gather patterns that coincide, as distinct patterns may once folded, into
+/// one, merging their tallies.
+pub fn coalesce<I, T, M>(iter : I, merge : M) -> BTreeMap<String, T>
+    where I : IntoIterator<Item = (String, T)>,
+          M : Fn(&mut T, T)
+{
+    let mut patterns = BTreeMap::new();
+    for (pattern, tally) in iter {
+        match patterns.entry(pattern) {
+            Entry::Vacant(e) => {
+                e.insert(tally);
+            }
+            Entry::Occupied(mut e) => merge(e.get_mut(), tally),
+        }
+    }
+    patterns
+}
+
 /// A specialized hashmap associating words to their known hyphenation.
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Exceptions(pub HashMap<String, Vec<usize>>);
 
 /// A dictionary for standard Knuth–Liang hyphenation.
 ///
-/// It comprises the working language, the pattern and exception sets,
-/// and the character boundaries for hyphenation.
+/// It comprises the working language, the pattern and exception sets, the
+/// options that limit hyphenation, and the normalization form of its
+/// patterns.
+///
+/// The pattern trie is stored in bytes of type `D`. Dictionaries loaded
+/// through [`Load`] own their bytes, while `Standard<&[u8]>` borrows them from
+/// a buffer – such as a memory-mapped file, or an embedded resource – without
+/// copying.
+///
+/// [`Load`]: ../../hyphenation/load/trait.Load.html
 #[derive(Clone, Debug, Serialize, Deserialize)]
-pub struct Standard {
+#[serde(bound(serialize = "", deserialize = "Patterns<D>: serde::Deserialize<'de>"))]
+pub struct Standard<D : AsRef<[u8]> = Vec<u8>> {
     language :       Language,
-    patterns :       Patterns,
+    patterns :       Patterns<D>,
     pub exceptions : Exceptions,
-    /// The minimum number of `char`s from the start and end of a word where
-    /// breaks may not occur.
-    pub minima :     (usize, usize),
+    /// The limits within which words are hyphenated, such as the minimum
+    /// number of graphemes from the start and end of a word where breaks may
+    /// not occur.
+    pub options :    HyphenationOptions,
+    normalization :  Normalization,
 }
 
 
-impl Standard {
+impl<D : AsRef<[u8]>> Standard<D> {
     /// The language for which this dictionary can provide hyphenation.
     pub fn language(&self) -> Language { self.language }
 
+    /// The normalization form of the patterns and exceptions, to which words
+    /// should be normalized before hyphenation.
+    pub fn normalization(&self) -> Normalization { self.normalization }
+
     /// An iterator over the tallies associated to all prefixes of the query,
     /// including the query itself.
-    pub fn prefix_tallies<'f, 'q>(&'f self, query : &'q [u8]) -> PrefixTallies<'f, 'q, Vec<Locus>> {
+    pub fn prefix_tallies<'f, 'q>(&'f self,
+                                  query : &'q [u8])
+                                  -> PrefixTallies<'f, 'q, Vec<Locus>, D> {
         PrefixTallies { matches : self.patterns.automaton.get_prefixes(query),
                         tallies : &self.patterns.tallies, }
     }
 }
 
-pub struct PrefixTallies<'f, 'q, T> {
+pub struct PrefixTallies<'f, 'q, T, D : AsRef<[u8]> = Vec<u8>> {
     tallies : &'f [T],
-    matches : PrefixMatches<'f, 'q>,
+    matches : PrefixMatches<'f, 'q, D>,
 }
 
-impl<'f, 'q, T> Iterator for PrefixTallies<'f, 'q, T> {
+impl<'f, 'q, T, D : AsRef<[u8]>> PrefixTallies<'f, 'q, T, D> {
+    /// The length in bytes of the prefix whose tally was last returned.
+    pub fn matched_length(&self) -> usize { self.matches.matched_length() }
+}
+
+impl<'f, 'q, T, D : AsRef<[u8]>> Iterator for PrefixTallies<'f, 'q, T, D> {
     type Item = &'f T;
 
     fn next(&mut self) -> Option<Self::Item> {
@@ -86,17 +149,19 @@ impl<'f, 'q, T> Iterator for PrefixTallies<'f, 'q, T> {
 /// dictionary.
 #[derive(Debug)]
 pub struct Builder {
-    pub language :   Language,
-    pub patterns :   Patterns,
-    pub exceptions : Exceptions,
+    pub language :      Language,
+    pub patterns :      Patterns,
+    pub exceptions :    Exceptions,
+    pub normalization : Normalization,
 }
 
 impl From<Builder> for Standard {
     fn from(b : Builder) -> Standard {
-        Standard { language :   b.language,
-                   patterns :   b.patterns,
-                   exceptions : b.exceptions,
-                   minima :     b.language.minima(), }
+        Standard { language :      b.language,
+                   patterns :      b.patterns,
+                   exceptions :    b.exceptions,
+                   options :       b.language.into(),
+                   normalization : b.normalization, }
     }
 }
 
@@ -9,42 +9,65 @@ use std::fmt;
 use std::slice;
 
 
-#[derive(Clone, Debug, Default)]
-pub struct Trie(Map<Vec<u8>>);
+/// A finite state transducer mapping patterns to the indices of their tallies,
+/// stored in bytes of type `D`: owned by default, but possibly borrowed from a
+/// buffer or memory map.
+#[derive(Clone)]
+pub struct Trie<D : AsRef<[u8]> = Vec<u8>>(Map<D>);
 
 impl Trie {
-    pub fn as_bytes(&self) -> &[u8] { self.0.as_fst().as_bytes() }
-
-    pub fn from_bytes(bs : Vec<u8>) -> Result<Self, Error> {
-        let map = Map::new(bs)?;
-        Ok(Trie(map))
-    }
-
     pub fn from_iter<I>(iter : I) -> Result<Self, Error>
         where I : Iterator<Item = (String, u64)>
     {
         let m = fst::Map::from_iter(iter)?;
         Ok(Trie(m))
     }
+}
+
+impl<D : AsRef<[u8]>> Trie<D> {
+    pub fn as_bytes(&self) -> &[u8] { self.0.as_fst().as_bytes() }
+
+    pub fn from_bytes(bs : D) -> Result<Self, Error> {
+        let map = Map::new(bs)?;
+        Ok(Trie(map))
+    }
 
-    pub fn get_prefixes<'f, 'q>(&'f self, query : &'q [u8]) -> PrefixMatches<'f, 'q> {
+    pub fn get_prefixes<'f, 'q>(&'f self, query : &'q [u8]) -> PrefixMatches<'f, 'q, D> {
         let fst = self.0.as_fst();
         PrefixMatches { fst,
                         node : fst.root(),
                         output : raw::Output::zero(),
+                        length : query.len(),
                         query : query.iter() }
     }
 }
 
+impl Default for Trie {
+    fn default() -> Self { Trie(Map::default()) }
+}
+
+impl<D : AsRef<[u8]>> fmt::Debug for Trie<D> {
+    fn fmt(&self, f : &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Trie").field(&self.0).finish()
+    }
+}
+
 #[derive(Clone)]
-pub struct PrefixMatches<'f, 'q> {
-    fst :    &'f raw::Fst<Vec<u8>>,
+pub struct PrefixMatches<'f, 'q, D = Vec<u8>> {
+    fst :    &'f raw::Fst<D>,
     node :   raw::Node<'f>,
     output : raw::Output,
+    length : usize,
     query :  slice::Iter<'q, u8>,
 }
 
-impl<'f, 'q> Iterator for PrefixMatches<'f, 'q> {
+impl<'f, 'q, D> PrefixMatches<'f, 'q, D> {
+    /// The length in bytes of the prefix last matched, or of the query
+    /// consumed so far.
+    pub fn matched_length(&self) -> usize { self.length - self.query.len() }
+}
+
+impl<'f, 'q, D : AsRef<[u8]>> Iterator for PrefixMatches<'f, 'q, D> {
     type Item = u64;
 
     fn next(&mut self) -> Option<u64> {
@@ -69,16 +92,16 @@ impl<'f, 'q> Iterator for PrefixMatches<'f, 'q> {
 }
 
 
-impl AsRef<fst::Map<Vec<u8>>> for Trie {
-    fn as_ref(&self) -> &fst::Map<Vec<u8>> { &self.0 }
+impl<D : AsRef<[u8]>> AsRef<fst::Map<D>> for Trie<D> {
+    fn as_ref(&self) -> &fst::Map<D> { &self.0 }
 }
 
-impl AsMut<fst::Map<Vec<u8>>> for Trie {
-    fn as_mut(&mut self) -> &mut fst::Map<Vec<u8>> { &mut self.0 }
+impl<D : AsRef<[u8]>> AsMut<fst::Map<D>> for Trie<D> {
+    fn as_mut(&mut self) -> &mut fst::Map<D> { &mut self.0 }
 }
 
-impl From<fst::Map<Vec<u8>>> for Trie {
-    fn from(m : fst::Map<Vec<u8>>) -> Self { Trie(m) }
+impl<D : AsRef<[u8]>> From<fst::Map<D>> for Trie<D> {
+    fn from(m : fst::Map<D>) -> Self { Trie(m) }
 }
 
 
@@ -117,7 +140,32 @@ impl<'de> Visitor<'de> for FstVisitor {
     }
 }
 
-impl Serialize for Trie {
+/// A visitor borrowing the trie's bytes from the deserializer's input, which
+/// must then outlive the trie.
+#[derive(Copy, Clone, Debug)]
+struct BorrowedFstVisitor;
+
+impl<'de> Visitor<'de> for BorrowedFstVisitor {
+    type Value = Trie<&'de [u8]>;
+
+    fn expecting(&self, f : &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("the internal trie of a hyphenation dictionary, as borrowed bytes")
+    }
+
+    fn visit_borrowed_bytes<E>(self, bs : &'de [u8]) -> Result<Self::Value, E>
+        where E : de::Error
+    {
+        Trie::from_bytes(bs).map_err(E::custom)
+    }
+
+    fn visit_newtype_struct<D>(self, de : D) -> Result<Self::Value, D::Error>
+        where D : Deserializer<'de>
+    {
+        de.deserialize_bytes(BorrowedFstVisitor)
+    }
+}
+
+impl<D : AsRef<[u8]>> Serialize for Trie<D> {
     fn serialize<S>(&self, ser : S) -> Result<S::Ok, S::Error>
         where S : Serializer
     {
@@ -133,6 +181,14 @@ impl<'de> Deserialize<'de> for Trie {
     }
 }
 
+impl<'de : 'a, 'a> Deserialize<'de> for Trie<&'a [u8]> {
+    fn deserialize<D>(de : D) -> Result<Self, D::Error>
+        where D : Deserializer<'de>
+    {
+        de.deserialize_newtype_struct(NOM_DE_SER, BorrowedFstVisitor)
+    }
+}
+
 
 #[derive(Debug)]
 pub struct Error(pub fst::Error);
@@ -0,0 +1,145 @@
+//! Unicode case folding, for the caseless matching of words against patterns.
+//!
+//! Patterns and exceptions are folded when dictionaries are built, and words
+//! likewise before hyphenation, so that "HYPHENATION", "Hyphenation" and
+//! "hyphenation" are all hyphenated alike. Folding follows the full case
+//! folding of the Unicode Standard (§ 3.13), which may change the length of a
+//! string: German "ß" and "ẞ" fold to "ss".
+//!
+//! Folding departs from the Standard in one respect: "İ" folds to plain "i",
+//! not "i\u{307}", since no pattern expects the combining dot, which would
+//! otherwise keep words such as "MİCRO" from matching any.
+//!
+//! Some languages tailor folding to their orthography:
+//!
+//! - In Turkish and Azerbaijani, dotted and dotless *i* are distinct letters,
+//!   such that "I" folds to "ı" and "İ" to "i".
+//! - In Lithuanian, *i* and *j* retain their dot under accents, such that "Í"
+//!   folds to "i\u{307}\u{301}", and "I" followed by an accent above to
+//!   "i\u{307}". "İ" also keeps its dot, as "i\u{307}".
+
+use std::borrow::Cow;
+
+use unicode_normalization::char::canonical_combining_class;
+
+use crate::language::Language;
+use crate::normalization::Normalization;
+
+
+/// The case folding of a language.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Folding {
+    /// Full case folding, as specified by Unicode.
+    #[default]
+    Default,
+    /// Full case folding, save for the dotted and dotless *i* of Turkish and
+    /// Azerbaijani.
+    Turkic,
+    /// Full case folding, retaining the dot of *i* and *j* under accents, as
+    /// in Lithuanian.
+    Lithuanian,
+}
+
+impl Folding {
+    /// Fold the string, borrowing it if it is already folded.
+    pub fn apply<'s>(&self, s : &'s str) -> Cow<'s, str> {
+        let mut chars = s.char_indices();
+        let first = loop {
+            match chars.next() {
+                None => return Cow::Borrowed(s),
+                Some((i, c)) if !self.preserves(c, &s[i + c.len_utf8() ..]) => break i,
+                _ => (),
+            }
+        };
+
+        let mut folded = String::with_capacity(s.len());
+        folded.push_str(&s[.. first]);
+        for (i, c) in s[first ..].char_indices() {
+            self.fold_char(c, &s[first + i + c.len_utf8() ..], &mut folded);
+        }
+        Cow::Owned(folded)
+    }
+
+    /// Fold the character, given the text that follows it, and push it onto
+    /// the string.
+    pub fn fold_char(&self, c : char, following : &str, folded : &mut String) {
+        match (*self, c) {
+            (Folding::Turkic, 'I') => folded.push('ı'),
+            (Folding::Turkic, 'İ') | (Folding::Default, 'İ') => folded.push('i'),
+            (Folding::Lithuanian, 'Ì') => folded.push_str("i\u{307}\u{300}"),
+            (Folding::Lithuanian, 'Í') => folded.push_str("i\u{307}\u{301}"),
+            (Folding::Lithuanian, 'Ĩ') => folded.push_str("i\u{307}\u{303}"),
+            (Folding::Lithuanian, 'I')
+            | (Folding::Lithuanian, 'J')
+            | (Folding::Lithuanian, 'Į')
+                if is_more_above(following) =>
+            {
+                folded.extend(c.to_lowercase());
+                folded.push('\u{307}');
+            }
+            // Dotless *i* has no case pair of its own: its uppercase is "I".
+            (_, 'ı') => folded.push('ı'),
+            // Cherokee was encoded in uppercase first, and folds to it.
+            _ if is_cherokee(c) => folded.extend(c.to_uppercase()),
+            // Folding maps a character to the lowercase of its uppercase, so
+            // that characters of a same case pair fold alike: "ſ" and "s", "ß"
+            // and "ẞ". Some uppercase characters lowercase to others outside
+            // their pair, hence the first lowercasing.
+            _ => {
+                for u in c.to_lowercase().flat_map(char::to_uppercase) {
+                    folded.extend(u.to_lowercase());
+                }
+            }
+        }
+    }
+
+    /// Whether the character folds to itself, given the text that follows
+    /// it.
+    fn preserves(&self, c : char, following : &str) -> bool {
+        if c.is_ascii() {
+            return !c.is_ascii_uppercase();
+        }
+        let mut folded = String::with_capacity(4);
+        self.fold_char(c, following, &mut folded);
+        folded.chars().eq(Some(c))
+    }
+}
+
+/// Whether the text starts with an accent above, before any base character,
+/// as by the `More_Above` condition of Unicode special casing.
+fn is_more_above(following : &str) -> bool {
+    for c in following.chars() {
+        match canonical_combining_class(c) {
+            230 => return true,
+            0 => return false,
+            _ => (),
+        }
+    }
+    false
+}
+
+fn is_cherokee(c : char) -> bool {
+    matches!(c, '\u{13a0}' ..= '\u{13f5}' | '\u{13f8}' ..= '\u{13fd}' | '\u{ab70}' ..= '\u{abbf}')
+}
+
+
+/// The form in which words are matched against patterns without regard to
+/// case: normalized, folded, and normalized again, since folding may leave
+/// strings denormalized, as when "ǰ" folds to "j\u{30c}".
+pub fn caseless(s : &str, normalization : Normalization, folding : Folding) -> String {
+    let normalized = normalization.apply(s);
+    let folded = folding.apply(&normalized);
+    normalization.apply(&folded).into_owned()
+}
+
+
+impl Language {
+    /// The case folding suited to the language.
+    pub fn folding(self) -> Folding {
+        match self {
+            Language::Turkish => Folding::Turkic,
+            Language::Lithuanian => Folding::Lithuanian,
+            _ => Folding::Default,
+        }
+    }
+}
@@ -0,0 +1,279 @@
+//! The header of serialized dictionaries.
+//!
+//! Dictionary files begin with a header identifying their format and contents,
+//! followed by the bincode-serialized dictionary itself. All integers are
+//! little-endian.
+//!
+//! ```text
+//! magic     8 bytes   "HYPHDICT"
+//! version   u16       format version
+//! kind      u8        0 for `Standard`, 1 for `Extended`
+//! language  u8 + str  language code, prefixed by its length
+//! source    u8 + str  version of the source patterns, prefixed by its length
+//! length    u64       length of the serialized dictionary
+//! checksum  u32       CRC-32 of the serialized dictionary
+//! ```
+
+use std::error;
+use std::fmt;
+use std::io::{self, Read};
+
+use crate::language::Language;
+
+
+/// The bytes that open every dictionary file.
+pub const MAGIC : &[u8; 8] = b"HYPHDICT";
+
+/// The version of the dictionary format written by this crate. Dictionaries
+/// of any other version cannot be read.
+pub const VERSION : u16 = 2;
+
+
+/// The type of dictionary held in a file.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Kind {
+    Standard,
+    Extended,
+}
+
+impl fmt::Display for Kind {
+    fn fmt(&self, f : &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Kind::Standard => f.write_str("standard"),
+            Kind::Extended => f.write_str("extended"),
+        }
+    }
+}
+
+
+/// A description of the dictionary held in a file.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Header {
+    /// The version of the dictionary format.
+    pub version :  u16,
+    pub kind :     Kind,
+    pub language : Language,
+    /// The version of the patterns the dictionary was built from, such as
+    /// `CTAN 2021-03-22`, or an empty string if unknown.
+    pub source :   String,
+}
+
+impl Header {
+    /// A header for a dictionary in the current format. Sources longer than
+    /// the 255 bytes a header holds are cut short, at a character boundary.
+    pub fn new(kind : Kind, language : Language, source : &str) -> Self {
+        Header { version : VERSION,
+                 kind,
+                 language,
+                 source : truncated(source).to_owned() }
+    }
+
+    /// Write the header, followed by the serialized dictionary.
+    pub fn write<W>(&self, writer : &mut W, body : &[u8]) -> io::Result<()>
+        where W : io::Write
+    {
+        let code = self.language.code().as_bytes();
+        let source = truncated(&self.source).as_bytes();
+
+        writer.write_all(MAGIC)?;
+        writer.write_all(&self.version.to_le_bytes())?;
+        writer.write_all(&[self.kind as u8, code.len() as u8])?;
+        writer.write_all(code)?;
+        writer.write_all(&[source.len() as u8])?;
+        writer.write_all(source)?;
+        writer.write_all(&(body.len() as u64).to_le_bytes())?;
+        writer.write_all(&crc32(body).to_le_bytes())?;
+        writer.write_all(body)
+    }
+
+    /// Read the header at the start of the bytes, and verify the serialized
+    /// dictionary that follows, which is returned alongside the header.
+    pub fn read(bytes : &[u8]) -> Result<(Header, &[u8]), Error> {
+        let mut cursor = bytes;
+        let (header, length, checksum) = Header::read_fields(&mut cursor)?;
+        let body = cursor.get(.. length).ok_or(Error::Truncated)?;
+        verify(body, checksum)?;
+
+        Ok((header, body))
+    }
+
+    /// Read the header from the reader, and verify the serialized dictionary
+    /// that follows, which is returned alongside the header.
+    pub fn read_from<R>(reader : &mut R) -> Result<(Header, Vec<u8>), Error>
+        where R : io::Read
+    {
+        let (header, length, checksum) = Header::read_fields(reader)?;
+        let mut body = vec![];
+        reader.take(length as u64).read_to_end(&mut body)?;
+        if body.len() < length {
+            return Err(Error::Truncated);
+        }
+        verify(&body, checksum)?;
+
+        Ok((header, body))
+    }
+
+    fn read_fields<R>(reader : &mut R) -> Result<(Header, usize, u32), Error>
+        where R : io::Read
+    {
+        let mut magic = [0; 8];
+        read_exact(reader, &mut magic)?;
+        if &magic != MAGIC {
+            return Err(Error::Magic);
+        }
+
+        let mut version = [0; 2];
+        read_exact(reader, &mut version)?;
+        let version = u16::from_le_bytes(version);
+        if version != VERSION {
+            return Err(Error::Version(version));
+        }
+
+        let kind = match read_byte(reader)? {
+            0 => Kind::Standard,
+            1 => Kind::Extended,
+            k => return Err(Error::Kind(k)),
+        };
+        let code = read_string(reader)?;
+        let language = Language::try_from_code(&code).ok_or(Error::Language(code))?;
+        let source = read_string(reader)?;
+
+        let mut length = [0; 8];
+        read_exact(reader, &mut length)?;
+        let mut checksum = [0; 4];
+        read_exact(reader, &mut checksum)?;
+
+        Ok((Header { version, kind, language, source },
+            u64::from_le_bytes(length) as usize,
+            u32::from_le_bytes(checksum)))
+    }
+}
+
+
+fn read_exact<R : io::Read>(reader : &mut R, buffer : &mut [u8]) -> Result<(), Error> {
+    reader.read_exact(buffer).map_err(|e| match e.kind() {
+                                 io::ErrorKind::UnexpectedEof => Error::Truncated,
+                                 _ => Error::IO(e),
+                             })
+}
+
+fn read_byte<R : io::Read>(reader : &mut R) -> Result<u8, Error> {
+    let mut byte = [0];
+    read_exact(reader, &mut byte)?;
+    Ok(byte[0])
+}
+
+fn read_string<R : io::Read>(reader : &mut R) -> Result<String, Error> {
+    let mut buffer = vec![0; read_byte(reader)? as usize];
+    read_exact(reader, &mut buffer)?;
+    String::from_utf8(buffer).map_err(|_| Error::Encoding)
+}
+
+/// The longest prefix of the string that fits in the 255 bytes allotted to
+/// strings in the header, ending on a character boundary.
+fn truncated(s : &str) -> &str {
+    let mut end = s.len().min(u8::MAX as usize);
+    while !s.is_char_boundary(end) {
+        end -= 1;
+    }
+    &s[.. end]
+}
+
+fn verify(body : &[u8], expected : u32) -> Result<(), Error> {
+    let found = crc32(body);
+    if found != expected {
+        Err(Error::Checksum { expected, found })
+    } else {
+        Ok(())
+    }
+}
+
+
+const CRC_TABLE : [u32; 256] = crc_table();
+
+const fn crc_table() -> [u32; 256] {
+    let mut table = [0; 256];
+    let mut n = 0;
+    while n < 256 {
+        let mut c = n as u32;
+        let mut k = 0;
+        while k < 8 {
+            c = if c & 1 != 0 {
+                0xedb8_8320 ^ (c >> 1)
+            } else {
+                c >> 1
+            };
+            k += 1;
+        }
+        table[n] = c;
+        n += 1;
+    }
+    table
+}
+
+/// The CRC-32 checksum of the bytes, as used by zlib and PNG.
+pub fn crc32(bytes : &[u8]) -> u32 {
+    !bytes.iter().fold(!0, |c, &b| {
+                     CRC_TABLE[((c ^ b as u32) & 0xff) as usize] ^ (c >> 8)
+                 })
+}
+
+
+/// Failure modes of header reading.
+#[derive(Debug)]
+pub enum Error {
+    /// The file does not begin with the expected magic bytes, and is most
+    /// likely not a dictionary, or a dictionary from an earlier version of
+    /// this crate.
+    Magic,
+    /// The dictionary is in an unsupported format version.
+    Version(u16),
+    /// The dictionary kind is not known.
+    Kind(u8),
+    /// The language code is not known.
+    Language(String),
+    /// A string in the header is not valid UTF-8.
+    Encoding,
+    /// The file ends before the dictionary does.
+    Truncated,
+    /// The dictionary does not match its checksum.
+    Checksum { expected : u32, found : u32 },
+    /// The file could not be read.
+    IO(io::Error),
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match *self {
+            Error::IO(ref e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f : &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::Magic => f.write_str("not a hyphenation dictionary"),
+            Error::Version(v) => {
+                write!(f,
+                       "unsupported dictionary format version {} (expected {})",
+                       v, VERSION)
+            }
+            Error::Kind(k) => write!(f, "unknown dictionary kind {}", k),
+            Error::Language(ref code) => write!(f, "unknown language code `{}`", code),
+            Error::Encoding => f.write_str("the dictionary header is not valid UTF-8"),
+            Error::Truncated => f.write_str("the dictionary is truncated"),
+            Error::Checksum { expected, found } => {
+                write!(f,
+                       "the dictionary is corrupted (checksum {:08x}, expected {:08x})",
+                       found, expected)
+            }
+            Error::IO(ref e) => e.fmt(f),
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(err : io::Error) -> Error { Error::IO(err) }
+}
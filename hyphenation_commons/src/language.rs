@@ -2,11 +2,13 @@
 
 use std::fmt;
 
+use crate::header::Kind;
+
 macro_rules! fiant_linguae {
-    ( $($lang:ident, $bounds:expr, $code:expr;)* ) => {
-        fiant_linguae! { $($lang, $bounds, $code);* }
+    ( $($lang:ident, $bounds:expr, $code:expr, $scripts:expr, $english:expr, $native:expr;)* ) => {
+        fiant_linguae! { $($lang, $bounds, $code, $scripts, $english, $native);* }
     };
-    ( $($lang:ident, $bounds:expr, $code:expr);* ) => {
+    ( $($lang:ident, $bounds:expr, $code:expr, $scripts:expr, $english:expr, $native:expr);* ) => {
         /// The set of languages available for hyphenation.
         #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
         pub enum Language {
@@ -14,7 +16,7 @@ macro_rules! fiant_linguae {
         }
 
         impl Language {
-            /// The default number of characters from the start and end of a word
+            /// The default number of graphemes from the start and end of a word
             /// where breaks may not occur.
             pub fn minima(&self) -> (usize, usize) {
                 match *self {
@@ -29,15 +31,44 @@ macro_rules! fiant_linguae {
                 }
             }
 
-            /// Try and construct a Language from a [BCP 47](https://tools.ietf.org/html/bcp47) tag.
+            /// The [ISO 15924](https://unicode.org/iso15924/) codes of the scripts
+            /// covered by the patterns for this language, such as `Latn`. Most
+            /// patterns cover a single script.
+            pub fn scripts(&self) -> &'static [&'static str] {
+                match *self {
+                    $( Language::$lang => $scripts, )*
+                }
+            }
+
+            /// The name of this language in English.
+            pub fn english_name(&self) -> &'static str {
+                match *self {
+                    $( Language::$lang => $english, )*
+                }
+            }
+
+            /// The name of this language in the language itself.
+            pub fn native_name(&self) -> &'static str {
+                match *self {
+                    $( Language::$lang => $native, )*
+                }
+            }
+
+            /// Try and construct a Language from its [BCP 47](https://tools.ietf.org/html/bcp47)
+            /// tag, regardless of case and of whether subtags are separated by
+            /// hyphens or underscores. Other tags are matched to a language by
+            /// [`from_tag`](#method.from_tag).
             pub fn try_from_code<T: AsRef<str>>(code: T) -> Option<Language> {
-                match code.as_ref() {
+                match code.as_ref().to_ascii_lowercase().replace('_', "-").as_str() {
                     $( $code => Some(Language::$lang), )*
                     _ => None
                 }
             }
         }
 
+        /// Every language available for hyphenation.
+        pub(crate) const LANGUAGES : &[Language] = &[ $( Language::$lang, )* ];
+
         impl fmt::Display for Language {
             fn fmt(&self, f : &mut fmt::Formatter) -> fmt::Result {
                 write!(f, "{:?}", *self)
@@ -47,93 +78,240 @@ macro_rules! fiant_linguae {
 }
 
 // NOTE: These hyphenation bounds were taken directly from the relevant TeX
-// packages, but it is not entirely clear how well they map to the notion of
-// Unicode `char` in Rust.
-//
-// In the worst case, a language featuring graphemes larger than 1 `char` may
-// set boundaries mid-grapheme. This should be of no practical consequence,
-// since well-formed hyphenation patterns only match full graphemes; moreover,
-// well-behaved hyphenators are expected to validate hyphenation opportunities,
-// discarding any which arise outside `char` boundaries.
+// packages, which count letters. Hyphenators count them in extended grapheme
+// clusters, such that a base letter and its combining marks, or an Indic
+// consonant and its vowel signs, count as one; and they discard opportunities
+// which arise within a grapheme.
+// Language, minima, code, scripts, English name, native name
 fiant_linguae! {
-    Afrikaans,              (1, 2),     "af";
-    Albanian,               (2, 2),     "sq";
-    Armenian,               (1, 2),     "hy";
-    Assamese,               (1, 1),     "as";
-    Basque,                 (2, 2),     "eu";
-    Belarusian,             (2, 2),     "be";
-    Bengali,                (1, 1),     "bn";
-    Bulgarian,              (2, 2),     "bg";
-    Catalan,                (2, 2),     "ca";
-    Chinese,                (1, 1),     "zh-latn-pinyin";
-    Coptic,                 (1, 1),     "cop";
-    Croatian,               (2, 2),     "hr";
-    Czech,                  (2, 3),     "cs";
-    Danish,                 (2, 2),     "da";
-    Dutch,                  (2, 2),     "nl";
-    EnglishGB,              (2, 3),     "en-gb";
-    EnglishUS,              (2, 3),     "en-us";
-    Esperanto,              (2, 2),     "eo";
-    Estonian,               (2, 3),     "et";
-    Ethiopic,               (1, 1),     "mul-ethi";
-    Finnish,                (2, 2),     "fi";
-    FinnishScholastic,      (1, 1),     "fi-x-school";
-    French,                 (2, 3),     "fr";
-    Friulan,                (2, 2),     "fur";
-    Galician,               (2, 2),     "gl";
-    Georgian,               (1, 2),     "ka";
-    German1901,             (2, 2),     "de-1901";
-    German1996,             (2, 2),     "de-1996";
-    GermanSwiss,            (2, 2),     "de-ch-1901";
-    GreekAncient,           (1, 1),     "grc";
-    GreekMono,              (1, 1),     "el-monoton";
-    GreekPoly,              (1, 1),     "el-polyton";
-    Gujarati,               (1, 1),     "gu";
-    Hindi,                  (1, 1),     "hi";
-    Hungarian,              (2, 2),     "hu";
-    Icelandic,              (2, 2),     "is";
-    Indonesian,             (2, 2),     "id";
-    Interlingua,            (2, 2),     "ia";
-    Irish,                  (2, 3),     "ga";
-    Italian,                (2, 2),     "it";
-    Kannada,                (1, 1),     "kn";
-    Kurmanji,               (2, 2),     "kmr";
-    Latin,                  (2, 2),     "la";
-    LatinClassic,           (2, 2),     "la-x-classic";
-    LatinLiturgical,        (2, 2),     "la-x-liturgic";
-    Latvian,                (2, 2),     "lv";
-    Lithuanian,             (2, 2),     "lt";
-    Macedonian,             (2, 2),     "mk";
-    Malayalam,              (1, 1),     "ml";
-    Marathi,                (1, 1),     "mr";
-    Mongolian,              (2, 2),     "mn-cyrl";
-    NorwegianBokmal,        (2, 2),     "nb";
-    NorwegianNynorsk,       (2, 2),     "nn";
-    Occitan,                (2, 2),     "oc";
-    Oriya,                  (1, 1),     "or";
-    Pali,                   (1, 2),     "pi";
-    Panjabi,                (1, 1),     "pa";
-    Piedmontese,            (2, 2),     "pms";
-    Polish,                 (2, 2),     "pl";
-    Portuguese,             (2, 3),     "pt";
-    Romanian,               (2, 2),     "ro";
-    Romansh,                (2, 2),     "rm";
-    Russian,                (2, 2),     "ru";
-    Sanskrit,               (1, 3),     "sa";
-    SerbianCyrillic,        (2, 2),     "sr-cyrl";
-    SerbocroatianCyrillic,  (2, 2),     "sh-cyrl";
-    SerbocroatianLatin,     (2, 2),     "sh-latn";
-    SlavonicChurch,         (1, 2),     "cu";
-    Slovak,                 (2, 3),     "sk";
-    Slovenian,              (2, 2),     "sl";
-    Spanish,                (2, 2),     "es";
-    Swedish,                (2, 2),     "sv";
-    Tamil,                  (1, 1),     "ta";
-    Telugu,                 (1, 1),     "te";
-    Thai,                   (2, 3),     "th";
-    Turkish,                (2, 2),     "tr";
-    Turkmen,                (2, 2),     "tk";
-    Ukrainian,              (2, 2),     "uk";
-    Uppersorbian,           (2, 2),     "hsb";
-    Welsh,                  (2, 3),     "cy";
+    Afrikaans,              (1, 2),     "af",             &["Latn"],  "Afrikaans",                        "Afrikaans";
+    Albanian,               (2, 2),     "sq",             &["Latn"],  "Albanian",                         "Shqip";
+    Armenian,               (1, 2),     "hy",             &["Armn"],  "Armenian",                         "Հայերեն";
+    Assamese,               (1, 1),     "as",             &["Beng"],  "Assamese",                         "অসমীয়া";
+    Basque,                 (2, 2),     "eu",             &["Latn"],  "Basque",                           "Euskara";
+    Belarusian,             (2, 2),     "be",             &["Cyrl"],  "Belarusian",                       "Беларуская";
+    Bengali,                (1, 1),     "bn",             &["Beng"],  "Bengali",                          "বাংলা";
+    Bulgarian,              (2, 2),     "bg",             &["Cyrl"],  "Bulgarian",                        "Български";
+    Catalan,                (2, 2),     "ca",             &["Latn"],  "Catalan",                          "Català";
+    Chinese,                (1, 1),     "zh-latn-pinyin", &["Latn"],  "Chinese (Pinyin)",                 "Hànyǔ Pīnyīn";
+    Coptic,                 (1, 1),     "cop",            &["Copt"],  "Coptic",                           "Ⲙⲉⲧⲣⲉⲙⲛ̀ⲭⲏⲙⲓ";
+    Croatian,               (2, 2),     "hr",             &["Latn"],  "Croatian",                         "Hrvatski";
+    Czech,                  (2, 3),     "cs",             &["Latn"],  "Czech",                            "Čeština";
+    Danish,                 (2, 2),     "da",             &["Latn"],  "Danish",                           "Dansk";
+    Dutch,                  (2, 2),     "nl",             &["Latn"],  "Dutch",                            "Nederlands";
+    EnglishGB,              (2, 3),     "en-gb",          &["Latn"],  "English (United Kingdom)",         "English (United Kingdom)";
+    EnglishUS,              (2, 3),     "en-us",          &["Latn"],  "English (United States)",          "English (United States)";
+    Esperanto,              (2, 2),     "eo",             &["Latn"],  "Esperanto",                        "Esperanto";
+    Estonian,               (2, 3),     "et",             &["Latn"],  "Estonian",                         "Eesti";
+    Ethiopic,               (1, 1),     "mul-ethi",       &["Ethi"],  "Ethiopic",                         "ግዕዝ";
+    Finnish,                (2, 2),     "fi",             &["Latn"],  "Finnish",                          "Suomi";
+    FinnishScholastic,      (1, 1),     "fi-x-school",    &["Latn"],  "Finnish (scholastic)",             "Suomi (koulutavutus)";
+    French,                 (2, 3),     "fr",             &["Latn"],  "French",                           "Français";
+    Friulan,                (2, 2),     "fur",            &["Latn"],  "Friulian",                         "Furlan";
+    Galician,               (2, 2),     "gl",             &["Latn"],  "Galician",                         "Galego";
+    Georgian,               (1, 2),     "ka",             &["Geor"],  "Georgian",                         "ქართული";
+    German1901,             (2, 2),     "de-1901",        &["Latn"],  "German (traditional orthography)", "Deutsch (traditionelle Rechtschreibung)";
+    German1996,             (2, 2),     "de-1996",        &["Latn"],  "German (reformed orthography)",    "Deutsch (reformierte Rechtschreibung)";
+    GermanSwiss,            (2, 2),     "de-ch-1901",     &["Latn"],  "German (Switzerland, traditional orthography)", "Deutsch (Schweiz, traditionelle Rechtschreibung)";
+    GreekAncient,           (1, 1),     "grc",            &["Grek"],  "Ancient Greek",                    "Ἀρχαία ἑλληνικὴ";
+    GreekMono,              (1, 1),     "el-monoton",     &["Grek"],  "Greek (monotonic)",                "Ελληνικά (μονοτονικό)";
+    GreekPoly,              (1, 1),     "el-polyton",     &["Grek"],  "Greek (polytonic)",                "Ἑλληνικὰ (πολυτονικό)";
+    Gujarati,               (1, 1),     "gu",             &["Gujr"],  "Gujarati",                         "ગુજરાતી";
+    Hindi,                  (1, 1),     "hi",             &["Deva"],  "Hindi",                            "हिन्दी";
+    Hungarian,              (2, 2),     "hu",             &["Latn"],  "Hungarian",                        "Magyar";
+    Icelandic,              (2, 2),     "is",             &["Latn"],  "Icelandic",                        "Íslenska";
+    Indonesian,             (2, 2),     "id",             &["Latn"],  "Indonesian",                       "Bahasa Indonesia";
+    Interlingua,            (2, 2),     "ia",             &["Latn"],  "Interlingua",                      "Interlingua";
+    Irish,                  (2, 3),     "ga",             &["Latn"],  "Irish",                            "Gaeilge";
+    Italian,                (2, 2),     "it",             &["Latn"],  "Italian",                          "Italiano";
+    Kannada,                (1, 1),     "kn",             &["Knda"],  "Kannada",                          "ಕನ್ನಡ";
+    Kurmanji,               (2, 2),     "kmr",            &["Latn"],  "Kurmanji",                         "Kurmancî";
+    Latin,                  (2, 2),     "la",             &["Latn"],  "Latin",                            "Latina";
+    LatinClassic,           (2, 2),     "la-x-classic",   &["Latn"],  "Latin (classical)",                "Latina classica";
+    LatinLiturgical,        (2, 2),     "la-x-liturgic",  &["Latn"],  "Latin (liturgical)",               "Latina liturgica";
+    Latvian,                (2, 2),     "lv",             &["Latn"],  "Latvian",                          "Latviešu";
+    Lithuanian,             (2, 2),     "lt",             &["Latn"],  "Lithuanian",                       "Lietuvių";
+    Macedonian,             (2, 2),     "mk",             &["Cyrl"],  "Macedonian",                       "Македонски";
+    Malayalam,              (1, 1),     "ml",             &["Mlym"],  "Malayalam",                        "മലയാളം";
+    Marathi,                (1, 1),     "mr",             &["Deva"],  "Marathi",                          "मराठी";
+    Mongolian,              (2, 2),     "mn-cyrl",        &["Cyrl"],  "Mongolian",                        "Монгол";
+    NorwegianBokmal,        (2, 2),     "nb",             &["Latn"],  "Norwegian Bokmål",                 "Norsk bokmål";
+    NorwegianNynorsk,       (2, 2),     "nn",             &["Latn"],  "Norwegian Nynorsk",                "Norsk nynorsk";
+    Occitan,                (2, 2),     "oc",             &["Latn"],  "Occitan",                          "Occitan";
+    Oriya,                  (1, 1),     "or",             &["Orya"],  "Oriya",                            "ଓଡ଼ିଆ";
+    Pali,                   (1, 2),     "pi",             &["Latn"],  "Pali",                             "Pāli";
+    Panjabi,                (1, 1),     "pa",             &["Guru"],  "Panjabi",                          "ਪੰਜਾਬੀ";
+    Piedmontese,            (2, 2),     "pms",            &["Latn"],  "Piedmontese",                      "Piemontèis";
+    Polish,                 (2, 2),     "pl",             &["Latn"],  "Polish",                           "Polski";
+    Portuguese,             (2, 3),     "pt",             &["Latn"],  "Portuguese",                       "Português";
+    Romanian,               (2, 2),     "ro",             &["Latn"],  "Romanian",                         "Română";
+    Romansh,                (2, 2),     "rm",             &["Latn"],  "Romansh",                          "Rumantsch";
+    Russian,                (2, 2),     "ru",             &["Cyrl"],  "Russian",                          "Русский";
+    Sanskrit,               (1, 3),     "sa",             &["Deva", "Beng", "Gujr", "Knda", "Mlym", "Telu", "Latn"], "Sanskrit",                         "संस्कृतम्";
+    SerbianCyrillic,        (2, 2),     "sr-cyrl",        &["Cyrl"],  "Serbian (Cyrillic)",               "Српски (ћирилица)";
+    SerbocroatianCyrillic,  (2, 2),     "sh-cyrl",        &["Cyrl"],  "Serbo-Croatian (Cyrillic)",        "Српскохрватски (ћирилица)";
+    SerbocroatianLatin,     (2, 2),     "sh-latn",        &["Latn"],  "Serbo-Croatian (Latin)",           "Srpskohrvatski (latinica)";
+    SlavonicChurch,         (1, 2),     "cu",             &["Cyrl"],  "Church Slavonic",                  "Церковнославѧ́нскїй ѧ҆зы́къ";
+    Slovak,                 (2, 3),     "sk",             &["Latn"],  "Slovak",                           "Slovenčina";
+    Slovenian,              (2, 2),     "sl",             &["Latn"],  "Slovenian",                        "Slovenščina";
+    Spanish,                (2, 2),     "es",             &["Latn"],  "Spanish",                          "Español";
+    Swedish,                (2, 2),     "sv",             &["Latn"],  "Swedish",                          "Svenska";
+    Tamil,                  (1, 1),     "ta",             &["Taml"],  "Tamil",                            "தமிழ்";
+    Telugu,                 (1, 1),     "te",             &["Telu"],  "Telugu",                           "తెలుగు";
+    Thai,                   (2, 3),     "th",             &["Thai"],  "Thai",                             "ไทย";
+    Turkish,                (2, 2),     "tr",             &["Latn"],  "Turkish",                          "Türkçe";
+    Turkmen,                (2, 2),     "tk",             &["Latn"],  "Turkmen",                          "Türkmençe";
+    Ukrainian,              (2, 2),     "uk",             &["Cyrl"],  "Ukrainian",                        "Українська";
+    Uppersorbian,           (2, 2),     "hsb",            &["Latn"],  "Upper Sorbian",                    "Hornjoserbšćina";
+    Welsh,                  (2, 3),     "cy",             &["Latn"],  "Welsh",                            "Cymraeg";
+}
+
+
+/// The version of the `hyph-utf8` patterns bundled with this crate.
+const HYPH_UTF8_VERSION : &str = "CTAN 2021-03-22";
+
+/// The directory of the `hyph-utf8` repository holding the original patterns
+/// of the bundled version.
+const HYPH_UTF8_PATTERNS : &str = "https://github.com/hyphenation/tex-hyphen/blob/\
+                                   49706f9cfa97f6ead26b473ec10d23d5a651318a/\
+                                   hyph-utf8/tex/generic/hyph-utf8/patterns/tex";
+
+/// The origin of the extended Catalan patterns.
+const CATALAN_URL : &str =
+    "http://www.softcatala.org/wiki/Rebost:Diccionari_català_de_partició_de_mots";
+
+/// The provenance of the patterns bundled for a language.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct PatternSource {
+    /// The upstream package of the patterns, such as `hyph-utf8`.
+    pub package : &'static str,
+    /// The version of the package from which the patterns were taken.
+    pub version : &'static str,
+    /// The location of the original patterns, whose header records their
+    /// authors and license in full.
+    pub url :     String,
+    /// The license of the patterns, as an SPDX expression. Licenses peculiar
+    /// to the patterns are given as `LicenseRef-` identifiers.
+    pub license : Option<&'static str>,
+    /// The authors of the patterns.
+    pub authors : &'static [&'static str],
+}
+
+impl Language {
+    /// An iterator over every language available for hyphenation.
+    pub fn all() -> impl Iterator<Item = Language> { LANGUAGES.iter().copied() }
+
+    /// The source of the patterns bundled for this language, for dictionaries
+    /// of the given kind, if any.
+    pub fn patterns(&self, kind : Kind) -> Option<PatternSource> {
+        match (kind, *self) {
+            (Kind::Standard, _) => {
+                let url = format!("{}/hyph-{}.tex", HYPH_UTF8_PATTERNS, self.code());
+                let (license, authors) = self.hyph_utf8_attribution();
+                Some(PatternSource { package : "hyph-utf8",
+                                     version : HYPH_UTF8_VERSION,
+                                     url,
+                                     license : Some(license),
+                                     authors })
+            }
+            (Kind::Extended, Language::Catalan) => {
+                Some(PatternSource { package : "Diccionari de partició de mots",
+                                     version : "1.3",
+                                     url :     CATALAN_URL.to_owned(),
+                                     license : Some("LGPL-3.0-or-later OR GPL-3.0-or-later"),
+                                     authors : &["Jaume Ortolà"], })
+            }
+            (Kind::Extended, Language::Hungarian) => {
+                Some(PatternSource { package : "huhyphn",
+                                     version : "v20110815",
+                                     url :     "http://www.github.hu/bencenagy/huhyphn".to_owned(),
+                                     license : Some("MPL-1.1 OR GPL-2.0-or-later OR LGPL-2.1-or-later"),
+                                     authors : &["Bence Nagy", "László Németh"], })
+            }
+            (Kind::Extended, _) => None,
+        }
+    }
+
+    /// The license and authors of the `hyph-utf8` patterns for this language,
+    /// as declared in their headers.
+    fn hyph_utf8_attribution(&self) -> (&'static str, &'static [&'static str]) {
+        use self::Language::*;
+
+        const LPPL : &str = "LPPL-1.3c";
+        const INDIC : &[&str] = &["Santhosh Thottingal"];
+        const GERMAN : &[&str] = &["Deutschsprachige Trennmustermannschaft"];
+        const GREEK : &[&str] = &["Dimitrios Filippou"];
+        const BECCARI : &[&str] = &["Claudio Beccari"];
+        const SERBIAN : &[&str] = &["Dejan Muhamedagić", "Aleksandar Jelenak"];
+
+        match *self {
+            Afrikaans => ("LGPL-2.1-or-later", &["Friedel Wolff"]),
+            Albanian => (LPPL, &["Igor Kirillov"]),
+            Armenian => ("LGPL-3.0-or-later", &["Sahak Petrosyan"]),
+            Basque => ("LicenseRef-hyph-utf8-eu", &["Juan M. Aguirregabiria"]),
+            Belarusian => ("MIT", &["Maksim Salau"]),
+            Bulgarian => (LPPL, &["Georgi Boshnakov"]),
+            Catalan => (LPPL, &["Gonçal Badenes", "Francesc Carmona"]),
+            Chinese => (LPPL, &["Jianrui Lyu"]),
+            Coptic => (LPPL, BECCARI),
+            Croatian => (LPPL, &["Igor Marinović"]),
+            Czech => ("GPL-2.0-or-later", &["Pavel Ševeček"]),
+            Danish => (LPPL, &["Frank Jensen"]),
+            Dutch => (LPPL, &["Piet Tutelaers", "Nico Poppelier", "Hans van der Meer"]),
+            EnglishGB => ("LicenseRef-hyph-utf8-en-gb", &["Dominik Wujastyk", "Graham Toal"]),
+            EnglishUS => ("Knuth-CTAN", &["Frank M. Liang", "Donald E. Knuth"]),
+            Esperanto => (LPPL, &["Sergei B. Pokrovsky"]),
+            Estonian => (LPPL, &["Enn Saar"]),
+            Ethiopic => ("MIT", &["Arthur Reutenauer", "Mojca Miklavec"]),
+            Finnish => ("LicenseRef-hyph-utf8-fi", &["Kauko Saarinen", "Fred Karlsson"]),
+            FinnishScholastic => ("MIT", &["Sakari Valtonen"]),
+            French => ("MIT", &["Daniel Flipo", "Bernard Gaulle", "Arnaud Delorme"]),
+            Friulan => (LPPL, BECCARI),
+            Galician => (LPPL, &["Javier A. Múgica"]),
+            Georgian => (LPPL, &["Levan Shoshiashvili"]),
+            German1901 | German1996 | GermanSwiss => ("MIT", GERMAN),
+            GreekAncient | GreekMono | GreekPoly => (LPPL, GREEK),
+            Assamese | Bengali | Gujarati | Hindi | Kannada | Malayalam | Marathi | Oriya
+            | Panjabi | Tamil | Telugu => ("MIT", INDIC),
+            Hungarian => ("MPL-1.1 OR GPL-2.0-or-later OR LGPL-2.1-or-later", &["Bence Nagy"]),
+            Icelandic => ("LPPL-1.2", &["Jörgen Pind", "Marteinn Sverrisson", "Kristinn Gylfason"]),
+            Indonesian => ("GPL-2.0-or-later", &["Jörg Knappen", "Terry Mart"]),
+            Interlingua => (LPPL, &["Peter Kleiweg"]),
+            Irish => ("GPL-2.0-or-later", &["Kevin P. Scannell"]),
+            Italian => (LPPL, &["Claudio Beccari", "Giuseppe Bilotta"]),
+            Kurmanji => (LPPL, &["Jörg Knappen", "Medeni Shemdê"]),
+            Latin | LatinClassic => (LPPL, &["Claudio Beccari"]),
+            LatinLiturgical => ("MIT", &["Claudio Beccari", "Monastery of Solesmes", "Élie Roux"]),
+            Latvian => ("LGPL-2.1-or-later", &["Jānis Vilims"]),
+            Lithuanian => (LPPL, &["Sigitas Tolušis", "Vytas Statulevičius"]),
+            Macedonian => (LPPL, &["Radovan Garabík"]),
+            Mongolian => (LPPL, &["Dorjgotov Batmunkh"]),
+            NorwegianBokmal | NorwegianNynorsk => {
+                ("LicenseRef-hyph-utf8-no", &["Rune Kleveland", "Ole Michael Selberg"])
+            }
+            Occitan => (LPPL, BECCARI),
+            Pali | Sanskrit => ("MIT", &["Yves Codet"]),
+            Piedmontese => (LPPL, BECCARI),
+            Polish => ("LicenseRef-hyph-utf8-pl",
+                       &["Hanna Kołodziejska", "Bogusław Jackowski", "Marek Ryćko"]),
+            Portuguese => ("BSD-3-Clause", &["Pedro J. de Rezende", "J. Joao Dias Almeida"]),
+            Romanian => (LPPL, &["Adrian Rezuș"]),
+            Romansh => (LPPL, BECCARI),
+            Russian => (LPPL, &["Alexander I. Lebedev", "Werner Lemberg", "Vladimir Volovich"]),
+            SerbianCyrillic | SerbocroatianCyrillic | SerbocroatianLatin => (LPPL, SERBIAN),
+            SlavonicChurch => (LPPL, &["Aleksandr Andreev", "Mike Kroutikov"]),
+            Slovak => ("GPL-2.0-or-later", &["Jana Chlebíková"]),
+            Slovenian => (LPPL, &["Matjaž Vrečko"]),
+            Spanish => (LPPL, &["Javier Bezos"]),
+            Swedish => ("LPPL-1.2", &["Jan Michael Rynning"]),
+            Thai => (LPPL, &["Theppitak Karoonboonyanan"]),
+            Turkish => {
+                (LPPL, &["Pierre A. MacKay", "H. Turgut Uyar", "S. Ekin Kocabas", "Mojca Miklavec"])
+            }
+            Turkmen => ("LicenseRef-hyph-utf8-tk", &["Nazar Annagurban"]),
+            Ukrainian => (LPPL, &["Maksym Polyakov", "Werner Lemberg", "Vladimir Volovich"]),
+            Uppersorbian => (LPPL, &["Eduard Werner"]),
+            Welsh => (LPPL, &["Yannis Haralambous"]),
+        }
+    }
 }
@@ -3,9 +3,18 @@ Hyphenation internals
 */
 
 #[macro_use] extern crate serde;
+extern crate unicode_normalization;
 
 pub mod dictionary;
+mod folding;
+pub mod header;
 mod language;
+mod normalization;
+mod options;
 pub mod parse;
+pub mod tag;
 
+pub use folding::*;
 pub use language::*;
+pub use normalization::*;
+pub use options::*;
@@ -0,0 +1,60 @@
+//! Unicode normalization forms.
+
+use std::borrow::Cow;
+
+use std::iter;
+
+use unicode_normalization::char::canonical_combining_class;
+use unicode_normalization::{is_nfc, is_nfc_quick, is_nfd, is_nfd_quick, is_nfkc, is_nfkc_quick,
+                            is_nfkd, is_nfkd_quick, IsNormalized, UnicodeNormalization};
+
+
+/// The normalization forms described by [Unicode Standard Annex #15](http://unicode.org/reports/tr15/).
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Normalization {
+    /// No normalization; strings are used as they are.
+    #[default]
+    None,
+    /// Canonical composition.
+    Nfc,
+    /// Canonical decomposition.
+    Nfd,
+    /// Compatibility composition.
+    Nfkc,
+    /// Compatibility decomposition.
+    Nfkd,
+}
+
+impl Normalization {
+    /// Normalize the string to this form, borrowing it if it is already
+    /// normalized.
+    pub fn apply<'s>(&self, s : &'s str) -> Cow<'s, str> {
+        match *self {
+            Normalization::None => Cow::Borrowed(s),
+            Normalization::Nfc if is_nfc(s) => Cow::Borrowed(s),
+            Normalization::Nfc => Cow::Owned(s.nfc().collect()),
+            Normalization::Nfd if is_nfd(s) => Cow::Borrowed(s),
+            Normalization::Nfd => Cow::Owned(s.nfd().collect()),
+            Normalization::Nfkc if is_nfkc(s) => Cow::Borrowed(s),
+            Normalization::Nfkc => Cow::Owned(s.nfkc().collect()),
+            Normalization::Nfkd if is_nfkd(s) => Cow::Borrowed(s),
+            Normalization::Nfkd => Cow::Owned(s.nfkd().collect()),
+        }
+    }
+
+    /// Whether strings normalized to this form may be divided before the
+    /// given character, such that the parts on either side, normalized
+    /// separately, make up the normalized whole. Such is the case before
+    /// characters that neither combine with those before them, nor reorder
+    /// among them.
+    pub fn is_boundary_before(&self, c : char) -> bool {
+        let quick = match *self {
+            Normalization::None => return true,
+            Normalization::Nfc => is_nfc_quick(iter::once(c)),
+            Normalization::Nfd => is_nfd_quick(iter::once(c)),
+            Normalization::Nfkc => is_nfkc_quick(iter::once(c)),
+            Normalization::Nfkd => is_nfkd_quick(iter::once(c)),
+        };
+        quick == IsNormalized::Yes && canonical_combining_class(c) == 0
+    }
+}
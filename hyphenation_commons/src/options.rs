@@ -0,0 +1,74 @@
+//! Limits on the hyphenation of words.
+//!
+//! Typesetting conventions restrict hyphenation beyond the breaks that
+//! patterns allow: TeX leaves `\lefthyphenmin` and `\righthyphenmin`
+//! letters unbroken at either end of a word, and CSS further spares short
+//! words altogether with `hyphenate-limit-chars`. Narrow columns may call
+//! for laxer limits than headlines, for which a single break per word may be
+//! more than enough.
+
+use crate::language::Language;
+
+
+/// The limits within which a word is hyphenated: the minima at either end,
+/// the length below which words are left whole, and the number of breaks
+/// allowed in a word.
+///
+/// Every dictionary carries its own options, which `hyphenate` applies;
+/// others may be given for a single word with `hyphenate_with`. Lengths are
+/// counted in extended grapheme clusters, such that a letter and its
+/// combining marks count as one.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct HyphenationOptions {
+    /// The minimum number of graphemes from the start and end of a word where
+    /// breaks may not occur.
+    pub minima :     (usize, usize),
+    /// The minimum number of graphemes in a word for it to be hyphenated at
+    /// all. Words shorter than the sum of the minima are never hyphenated,
+    /// whereas those as long may break between their two ends.
+    pub min_length : usize,
+    /// The greatest number of breaks to set in a word, if any. Should a word
+    /// have more, only the best are kept: those of the highest quality, and,
+    /// among equals, those nearest the middle of the word.
+    pub max_breaks : Option<usize>,
+}
+
+impl HyphenationOptions {
+    /// Options with the given minima, and no other limits.
+    pub fn new(minima : (usize, usize)) -> Self {
+        HyphenationOptions { minima,
+                             min_length : 0,
+                             max_breaks : None }
+    }
+
+    /// Set the minimum number of graphemes to leave before the first and
+    /// after the last break.
+    pub fn minima(mut self, left : usize, right : usize) -> Self {
+        self.minima = (left, right);
+        self
+    }
+
+    /// Leave words of fewer than `length` graphemes unhyphenated.
+    pub fn min_length(mut self, length : usize) -> Self {
+        self.min_length = length;
+        self
+    }
+
+    /// Set no more than `count` breaks in a word.
+    pub fn max_breaks(mut self, count : usize) -> Self {
+        self.max_breaks = Some(count);
+        self
+    }
+
+    /// The same options, with no limit on the number of breaks.
+    pub fn unlimited(self) -> Self {
+        HyphenationOptions { max_breaks : None,
+                             ..self }
+    }
+}
+
+impl From<Language> for HyphenationOptions {
+    /// The customary options for the language: its minima, and no other
+    /// limits.
+    fn from(language : Language) -> Self { HyphenationOptions::new(language.minima()) }
+}
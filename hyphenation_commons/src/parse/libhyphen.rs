@@ -0,0 +1,219 @@
+//! Hyphenation dictionaries in the format of libhyphen, as used by
+//! LibreOffice and Hunspell.
+//!
+//! A libhyphen dictionary (`hyph_xx_YY.dic`) begins with the name of its
+//! character set, followed by optional directives and one pattern per line.
+//! Patterns may be extended with the non-standard syntax described by Németh,
+//! which `Extended` dictionaries understand.
+//!
+//! ```text
+//! UTF-8
+//! LEFTHYPHENMIN 2
+//! RIGHTHYPHENMIN 2
+//! % comment
+//! .a2
+//! as5szon2y/sz=,2,1
+//! ```
+//!
+//! Besides UTF-8, the 8-bit character sets of the dictionaries distributed
+//! with libhyphen are understood: ISO 8859-1, -2, -5, -7, -9, -13 and -15,
+//! KOI8-R, KOI8-U, Windows-1251 and TIS-620.
+//!
+//! Dictionaries for compounding languages may have two levels, divided by
+//! `NEXTLEVEL`: libhyphen first breaks a word at the joints found by the
+//! patterns of the compound level, then hyphenates it with those of the
+//! second. This crate's dictionaries have a single level, and take the second,
+//! which libhyphen applies to every word alike. The compound level and the
+//! directives that concern it alone – `COMPOUNDLEFTHYPHENMIN`,
+//! `COMPOUNDRIGHTHYPHENMIN` and `NOHYPHEN` – are skipped, and recorded in
+//! [`Source::skipped`](struct.Source.html#structfield.skipped).
+
+use std::error;
+use std::fmt;
+use std::str;
+
+use crate::dictionary::extended as ext;
+use crate::dictionary::{self as dict, Standard};
+use crate::language::Language;
+use crate::normalization::Normalization;
+use crate::parse::{self, charset, Listing, Parse};
+
+
+/// The contents of a libhyphen dictionary.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Source {
+    /// The character set declared by the dictionary.
+    pub charset :  String,
+    /// The minima declared by `LEFTHYPHENMIN` and `RIGHTHYPHENMIN`, if any.
+    pub minima :   (Option<usize>, Option<usize>),
+    /// Hyphenation patterns, possibly in extended notation.
+    pub patterns : Vec<String>,
+    /// The directives of compound hyphenation that were skipped, with their
+    /// line. Where `NEXTLEVEL` is among them, the patterns that precede it,
+    /// which make up the compound level, were skipped as well.
+    pub skipped :  Vec<(String, usize)>,
+}
+
+impl Source {
+    /// Read a libhyphen dictionary, decoding it according to its declared
+    /// character set.
+    pub fn parse(bytes : &[u8]) -> Result<Self, Error> {
+        let first_line = bytes.split(|&b| b == b'\n').next().unwrap_or(&[]);
+        let charset = String::from_utf8_lossy(first_line).trim().to_owned();
+        let text = decode(&charset, bytes)?;
+        let mut source = Source { charset, ..Source::default() };
+
+        for (n, raw) in text.lines().enumerate().skip(1) {
+            let line = raw.trim();
+            if line.is_empty() || line.starts_with('%') || line.starts_with('#') {
+                continue;
+            }
+
+            let (keyword, argument) = match line.find(char::is_whitespace) {
+                Some(i) => (&line[.. i], line[i ..].trim()),
+                None => (line, ""),
+            };
+            let number = || match argument.parse::<usize>() {
+                Ok(min) => Ok(Some(min)),
+                Err(_) => Err(Error::Directive { directive : keyword.to_owned(),
+                                                 line :      n + 1, }),
+            };
+
+            match keyword {
+                "LEFTHYPHENMIN" => source.minima.0 = number()?,
+                "RIGHTHYPHENMIN" => source.minima.1 = number()?,
+                "NEXTLEVEL" => {
+                    // As in libhyphen, a dictionary has two levels at most.
+                    if source.skipped.iter().any(|(d, _)| d == "NEXTLEVEL") {
+                        break;
+                    }
+                    source.patterns.clear();
+                    source.skipped.push((keyword.to_owned(), n + 1));
+                }
+                "COMPOUNDLEFTHYPHENMIN" | "COMPOUNDRIGHTHYPHENMIN" | "NOHYPHEN" => {
+                    source.skipped.push((keyword.to_owned(), n + 1))
+                }
+                _ => {
+                    let indent = raw[.. raw.len() - raw.trim_start().len()].chars().count();
+                    ext::Patterns::tally(line).map_err(|e| e.at_line(n + 1).offset(indent))?;
+                    source.patterns.push(line.to_owned())
+                }
+            }
+        }
+
+        Ok(source)
+    }
+
+    /// The declared minima, defaulting to those of the given language.
+    pub fn minima_or(&self, language : Language) -> (usize, usize) {
+        let (l, r) = language.minima();
+        (self.minima.0.unwrap_or(l), self.minima.1.unwrap_or(r))
+    }
+
+    /// Build a `Standard` dictionary for the given language, normalizing
+    /// patterns to the given form and case-folding them. Extended patterns are
+    /// reduced to their standard portion.
+    pub fn standard(&self,
+                    language : Language,
+                    normalization : Normalization)
+                    -> Result<Standard, Error> {
+        self.listing(language, normalization).standard(|_, e| e)
+    }
+
+    /// Build an `Extended` dictionary for the given language, normalizing
+    /// patterns to the given form and case-folding them.
+    pub fn extended(&self,
+                    language : Language,
+                    normalization : Normalization)
+                    -> Result<ext::Extended, Error> {
+        self.listing(language, normalization).extended(|_, e| e)
+    }
+
+    fn listing(&self, language : Language, normalization : Normalization) -> Listing<'_, String> {
+        Listing { language,
+                  normalization,
+                  minima : Some(self.minima_or(language)),
+                  patterns : &self.patterns,
+                  exceptions : &[] }
+    }
+}
+
+/// Read a libhyphen dictionary into a `Standard` dictionary for the given
+/// language.
+pub fn standard(language : Language, bytes : &[u8]) -> Result<Standard, Error> {
+    Source::parse(bytes)?.standard(language, Normalization::None)
+}
+
+/// Read a libhyphen dictionary into an `Extended` dictionary for the given
+/// language.
+pub fn extended(language : Language, bytes : &[u8]) -> Result<ext::Extended, Error> {
+    Source::parse(bytes)?.extended(language, Normalization::None)
+}
+
+
+fn decode(charset : &str, bytes : &[u8]) -> Result<String, Error> {
+    match charset.to_ascii_uppercase().as_str() {
+        "UTF-8" | "UTF8" => str::from_utf8(bytes).map(str::to_owned)
+                                                 .map_err(|_| Error::Encoding),
+        "ISO8859-1" | "ISO-8859-1" | "ISO_8859-1" | "LATIN1" => {
+            Ok(bytes.iter().map(|&b| b as char).collect())
+        }
+        _ => match charset::table(charset) {
+            Some(table) => charset::decode(table, bytes).ok_or(Error::Encoding),
+            None => Err(Error::Charset(charset.to_owned())),
+        },
+    }
+}
+
+
+/// Failure modes of libhyphen dictionary parsing.
+#[derive(Debug)]
+pub enum Error {
+    /// The declared character set is not supported.
+    Charset(String),
+    /// The dictionary is not valid in its declared character set.
+    Encoding,
+    /// A directive was given an invalid argument.
+    Directive {
+        directive : String,
+        line :      usize,
+    },
+    /// A pattern is malformed.
+    Parse(parse::Error),
+    /// The dictionary's internal trie could not be built.
+    Trie(dict::Error),
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match *self {
+            Error::Parse(ref e) => Some(e),
+            Error::Trie(ref e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f : &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::Charset(ref charset) => write!(f, "unsupported character set `{}`", charset),
+            Error::Encoding => {
+                f.write_str("the dictionary is not valid in its declared character set")
+            }
+            Error::Directive { ref directive, line } => {
+                write!(f, "line {}: invalid argument to `{}`", line, directive)
+            }
+            Error::Parse(ref e) => e.fmt(f),
+            Error::Trie(ref e) => e.fmt(f),
+        }
+    }
+}
+
+impl From<parse::Error> for Error {
+    fn from(err : parse::Error) -> Error { Error::Parse(err) }
+}
+
+impl From<dict::Error> for Error {
+    fn from(err : dict::Error) -> Error { Error::Trie(err) }
+}
@@ -0,0 +1,457 @@
+//! Pattern and exception parsing.
+
+use std::error;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use crate::dictionary::extended::{self as ext, Subregion};
+use crate::dictionary::{self as dict, *};
+use crate::folding::caseless;
+use crate::language::Language;
+use crate::normalization::Normalization;
+
+mod charset;
+pub mod libhyphen;
+pub mod tex;
+
+pub trait Parse {
+    type Tally: Eq;
+
+    fn value(v : char) -> Option<u8>;
+    fn tally(s : &str) -> Result<Self::Tally, Error>;
+
+    fn alphabetical(s : &str) -> String {
+        s.chars()
+         .filter(|c| Self::value(*c).is_none())
+         .collect()
+    }
+
+    fn pair<N>(str_klp : &str, normalize : N) -> Result<(String, Self::Tally), Error>
+        where N : Fn(&str) -> String
+    {
+        let normalized = normalize(str_klp);
+        let tally = Self::tally(&normalized)?;
+        Ok((Self::alphabetical(&normalized), tally))
+    }
+}
+
+impl Parse for Patterns {
+    type Tally = Vec<Locus>;
+
+    #[inline]
+    fn value(c : char) -> Option<u8> { c.to_digit(10).map(|n| n as u8) }
+
+    fn tally(pattern : &str) -> Result<Self::Tally, Error> {
+        let mut tally = vec![];
+        let mut digits = 0;
+        let mut follows_digit = false;
+        for (column, (i, c)) in pattern.char_indices().enumerate() {
+            let value = match Self::value(c) {
+                Some(v) => v,
+                None => {
+                    follows_digit = false;
+                    continue;
+                }
+            };
+            // Two values cannot share the same position.
+            if follows_digit {
+                return Err(Error::new(pattern, column + 1, Problem::Digit(c)));
+            }
+            let index = i - digits;
+            if index > u8::MAX as usize {
+                return Err(Error::new(pattern,
+                                      column + 1,
+                                      Problem::IndexOverflow(index)));
+            }
+            tally.push(Locus { index : index as u8, value });
+            digits += 1;
+            follows_digit = true;
+        }
+
+        Ok(tally)
+    }
+}
+
+impl Parse for Exceptions {
+    type Tally = Vec<usize>;
+
+    #[inline]
+    fn value(c : char) -> Option<u8> {
+        match c {
+            '-' => Some(2),
+            _ => None,
+        }
+    }
+
+    fn tally(exception : &str) -> Result<Self::Tally, Error> {
+        let tally = exception.bytes()
+                             .enumerate()
+                             .filter_map(|(i, b)| Self::value(b as char).map(|_| i))
+                             .enumerate()
+                             .map(|(j, i)| i - j)
+                             .collect();
+        Ok(tally)
+    }
+}
+
+impl Parse for ext::Patterns {
+    type Tally = ext::Tally;
+
+    #[inline]
+    fn value(c : char) -> Option<u8> { c.to_digit(10).map(|n| n as u8) }
+
+    fn alphabetical(s : &str) -> String {
+        match s.find('/') {
+            None => Patterns::alphabetical(s),
+            Some(i) => Patterns::alphabetical(&s[.. i]),
+        }
+    }
+
+    /// Normalization may change the number of characters in a pattern, and
+    /// thus shift the subregion, whose indices count them. The standard
+    /// portion of extended patterns is thus normalized in pieces, divided at
+    /// the bounds of the subregion, and the indices counted anew.
+    fn pair<N>(str_klp : &str, normalize : N) -> Result<(String, Self::Tally), Error>
+        where N : Fn(&str) -> String
+    {
+        let normalized = match Extension::parse(str_klp)? {
+            None => normalize(str_klp),
+            Some(ext) => ext.normalize(normalize),
+        };
+        let tally = Self::tally(&normalized)?;
+        Ok((Self::alphabetical(&normalized), tally))
+    }
+
+    fn tally(pattern : &str) -> Result<Self::Tally, Error> {
+        let ext = match Extension::parse(pattern)? {
+            None => {
+                return Ok(ext::Tally { standard :  Patterns::tally(pattern)?,
+                                       subregion : None, })
+            }
+            Some(ext) => ext,
+        };
+        let column = |byte : usize| pattern[.. byte].chars().count() + 1;
+        let fail = |byte : usize, problem : Problem| Error::new(pattern, column(byte), problem);
+
+        let tally = Patterns::tally(ext.standard)?;
+        let alphabetical = Patterns::alphabetical(ext.standard);
+        let substitution = ext.sub_pattern
+                              .chars()
+                              .filter(|&c| c.is_alphabetic())
+                              .collect();
+        let (chars_to_start, chars_to_op, chars_to_end) = ext.bounds();
+        let nth = |n : usize| {
+            alphabetical.char_indices()
+                        .nth(n)
+                        .map(|(i, _)| i)
+                        .ok_or_else(|| fail(ext.indices_start, Problem::SubregionOutOfRange))
+        };
+        let start = nth(chars_to_start)?;
+        let end = nth(chars_to_end)?;
+        let index = nth(chars_to_op)?;
+        if index > u8::MAX as usize {
+            return Err(fail(ext.indices_start, Problem::IndexOverflow(index)));
+        }
+        if end < index || index < start {
+            return Err(fail(ext.indices_start, Problem::SubregionOutOfRange));
+        }
+        let (left, right) = (index - start, end - index);
+        let index = index as u8;
+        let value = tally.iter()
+                         .find(|&&locus| locus.index == index)
+                         .map(|&locus| locus.value)
+                         .ok_or_else(|| fail(ext.indices_start, Problem::Unmarked))?;
+
+        Ok(ext::Tally { standard :  tally,
+                        subregion : (Locus { index, value },
+                                     Subregion { left,
+                                                 right,
+                                                 substitution,
+                                                 breakpoint : ext.breakpoint })
+                                                                             .into(), })
+    }
+}
+
+/// The parts of an extended pattern, as written.
+struct Extension<'p> {
+    standard :      &'p str,
+    /// The substitution, with its breakpoint marked by `=`.
+    sub_pattern :   &'p str,
+    breakpoint :    usize,
+    /// The number of characters before the opportunity, and in the
+    /// subregion.
+    indices :       (usize, usize),
+    /// The byte index of the subregion indices within the pattern.
+    indices_start : usize,
+}
+
+impl<'p> Extension<'p> {
+    /// The extension of the pattern, if it has one.
+    fn parse(pattern : &'p str) -> Result<Option<Self>, Error> {
+        let slash = match pattern.find('/') {
+            None => return Ok(None),
+            Some(i) => i,
+        };
+        let column = |byte : usize| pattern[.. byte].chars().count() + 1;
+        let fail = |byte : usize, problem : Problem| Error::new(pattern, column(byte), problem);
+
+        let (standard, extension) = (&pattern[.. slash], &pattern[slash + 1 ..]);
+        let breakpoint = extension.find('=')
+                                  .ok_or_else(|| fail(pattern.len(), Problem::MissingBreakpoint))?;
+        let sub_pattern_end =
+            extension.find(',')
+                     .ok_or_else(|| fail(pattern.len(), Problem::MissingSeparator))?;
+        let sub_pattern = &extension[.. sub_pattern_end];
+        let indices_start = slash + 1 + sub_pattern_end + 1;
+        let sub_idxs = &pattern[indices_start ..];
+
+        let mut v = vec![];
+        let mut start = indices_start;
+        for s in sub_idxs.split(',') {
+            let n = usize::from_str(s).map_err(|_| fail(start, Problem::Number(s.to_owned())))?;
+            v.push(n);
+            start += s.len() + 1;
+        }
+        let indices = match v.len() {
+            2 => (v[0], v[1]),
+            n if n < 2 => return Err(fail(pattern.len(), Problem::MissingSeparator)),
+            _ => return Err(fail(indices_start, Problem::SubregionIndices(v.len()))),
+        };
+
+        Ok(Some(Extension { standard,
+                            sub_pattern,
+                            breakpoint,
+                            indices,
+                            indices_start }))
+    }
+
+    /// The number of characters of the standard portion, its leading dot
+    /// included, before the start of the subregion, the opportunity, and the
+    /// end of the subregion.
+    fn bounds(&self) -> (usize, usize, usize) {
+        let dot_offset = if self.standard.starts_with('.') { 1 } else { 0 };
+        let chars_to_op = self.indices.0 + dot_offset;
+        // Németh always starts the subregion at the character immediately
+        // preceding the opportunity.
+        let chars_to_start = chars_to_op.saturating_sub(1);
+        (chars_to_start, chars_to_op, chars_to_start + self.indices.1)
+    }
+
+    /// The pattern, normalized in pieces divided at the bounds of the
+    /// subregion, with its indices counted anew.
+    fn normalize<N>(&self, normalize : N) -> String
+        where N : Fn(&str) -> String
+    {
+        let (start, op, end) = self.bounds();
+        let letters = |s : &str| s.chars().filter(|&c| Patterns::value(c).is_none()).count();
+
+        let mut pieces = vec![];
+        let (mut from, mut seen) = (0, 0);
+        for (i, c) in self.standard.char_indices() {
+            if Patterns::value(c).is_some() {
+                continue;
+            }
+            if i > from && [start, op, end].contains(&seen) {
+                pieces.push((seen, &self.standard[from .. i]));
+                from = i;
+            }
+            seen += 1;
+        }
+        pieces.push((seen, &self.standard[from ..]));
+
+        // The count of letters at the end of each piece, before and after
+        // normalization.
+        let mut standard = String::with_capacity(self.standard.len());
+        let mut counts = vec![(0, 0)];
+        for (seen, piece) in pieces {
+            standard.push_str(&normalize(piece));
+            counts.push((seen, letters(&standard)));
+        }
+        let recount = |n : usize| {
+            counts.iter()
+                  .find(|&&(seen, _)| seen == n)
+                  .map_or(n, |&(_, count)| count)
+        };
+
+        let dot_offset = if self.standard.starts_with('.') { 1 } else { 0 };
+        format!("{}/{},{},{}",
+                standard,
+                normalize(self.sub_pattern),
+                recount(op) - dot_offset,
+                recount(end) - recount(start))
+    }
+}
+
+
+/// Patterns and exceptions as read from some source, and the settings of the
+/// dictionary to be built from them.
+#[derive(Clone, Copy, Debug)]
+pub struct Listing<'a, T> {
+    pub language :      Language,
+    pub normalization : Normalization,
+    /// The minima of the dictionary, if not those of its language.
+    pub minima :        Option<(usize, usize)>,
+    pub patterns :      &'a [T],
+    pub exceptions :    &'a [T],
+}
+
+impl<'a, T : AsRef<str>> Listing<'a, T> {
+    /// Build a `Standard` dictionary, normalizing patterns and exceptions to
+    /// the given form and case-folding them. Extended patterns are reduced to
+    /// their standard portion. Malformed entries are reported through `locate`,
+    /// which may attach their origin to the error.
+    pub fn standard<E, L>(&self, locate : L) -> Result<Standard, E>
+        where E : From<Error> + From<dict::Error>,
+              L : Fn(&T, Error) -> Error
+    {
+        let normalize = |s : &str| caseless(s, self.normalization, self.language.folding());
+        let standard = |p : &T| {
+            let text = p.as_ref();
+            let text = text.find('/').map_or(text, |i| &text[.. i]);
+            Patterns::pair(text, normalize).map_err(|e| locate(p, e))
+        };
+        let pairs : Vec<_> = self.patterns.iter().map(standard).collect::<Result<_, _>>()?;
+        let patterns = Patterns::from_iter(pairs)?;
+        let exceptions = self.exceptions
+                             .iter()
+                             .map(|e| {
+                                 Exceptions::pair(e.as_ref(), normalize)
+                                     .map_err(|err| locate(e, err))
+                             })
+                             .collect::<Result<_, _>>()?;
+
+        let mut dict : Standard = Builder { language : self.language,
+                                            patterns,
+                                            exceptions : Exceptions(exceptions),
+                                            normalization : self.normalization }.into();
+        dict.options.minima = self.minima.unwrap_or(dict.options.minima);
+        Ok(dict)
+    }
+
+    /// Build an `Extended` dictionary, normalizing patterns and exceptions to
+    /// the given form and case-folding them. Malformed entries are reported
+    /// through `locate`, which may attach their origin to the error.
+    pub fn extended<E, L>(&self, locate : L) -> Result<ext::Extended, E>
+        where E : From<Error> + From<dict::Error>,
+              L : Fn(&T, Error) -> Error
+    {
+        let normalize = |s : &str| caseless(s, self.normalization, self.language.folding());
+        let extended = |p : &T| ext::Patterns::pair(p.as_ref(), normalize).map_err(|e| locate(p, e));
+        let pairs : Vec<_> = self.patterns.iter().map(extended).collect::<Result<_, _>>()?;
+        let patterns = ext::Patterns::from_iter(pairs)?;
+        let exceptions = self.exceptions
+                             .iter()
+                             .map(|e| {
+                                 let pair = Exceptions::pair(e.as_ref(), normalize);
+                                 let (word, breaks) = pair.map_err(|err| locate(e, err))?;
+                                 Ok((word, breaks.into_iter().map(|i| (i, None)).collect()))
+                             })
+                             .collect::<Result<_, Error>>()?;
+
+        let mut dict : ext::Extended = ext::Builder { language : self.language,
+                                                      patterns,
+                                                      exceptions : ext::Exceptions(exceptions),
+                                                      normalization : self.normalization }.into();
+        dict.options.minima = self.minima.unwrap_or(dict.options.minima);
+        Ok(dict)
+    }
+}
+
+
+/// A malformed pattern or exception, and where it was found.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Error {
+    /// The file containing the pattern, if known.
+    pub file :    Option<PathBuf>,
+    /// The line of the pattern within its file, counting from 1, if known.
+    pub line :    Option<usize>,
+    /// The position of the problem in `char`s counting from 1: within the
+    /// line where the pattern was found, if known, and otherwise within the
+    /// pattern.
+    pub column :  usize,
+    /// The offending pattern.
+    pub pattern : String,
+    /// What is wrong with the pattern.
+    pub problem : Problem,
+}
+
+impl Error {
+    pub fn new(pattern : &str, column : usize, problem : Problem) -> Self {
+        Error { file : None,
+                line : None,
+                column,
+                pattern : pattern.to_owned(),
+                problem }
+    }
+
+    /// Record the line where the pattern was found.
+    pub fn at_line(mut self, line : usize) -> Self {
+        self.line = Some(line);
+        self
+    }
+
+    /// Count the column from the start of the line where the pattern was
+    /// found, the pattern being preceded by `chars` characters.
+    pub fn offset(mut self, chars : usize) -> Self {
+        self.column += chars;
+        self
+    }
+
+    /// Record the file where the pattern was found.
+    pub fn in_file<P : AsRef<Path>>(mut self, file : P) -> Self {
+        self.file = Some(file.as_ref().to_owned());
+        self
+    }
+}
+
+/// The ways in which a pattern may be malformed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Problem {
+    /// A digit immediately follows another, so that two values would share
+    /// the same position.
+    Digit(char),
+    /// The position of a value exceeds the 255 bytes allowed by dictionaries.
+    IndexOverflow(usize),
+    /// An extended pattern lacks the `=` marking its breakpoint.
+    MissingBreakpoint,
+    /// An extended pattern lacks a `,` before one of its subregion indices.
+    MissingSeparator,
+    /// A subregion index of an extended pattern is not a number.
+    Number(String),
+    /// An extended pattern has more than two subregion indices.
+    SubregionIndices(usize),
+    /// The subregion of an extended pattern extends beyond the pattern.
+    SubregionOutOfRange,
+    /// The opportunity of an extended pattern carries no value.
+    Unmarked,
+}
+
+impl error::Error for Error {}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f : &mut fmt::Formatter) -> fmt::Result {
+        if let Some(ref file) = self.file {
+            write!(f, "{}:", file.display())?;
+        }
+        if let Some(line) = self.line {
+            write!(f, "{}:", line)?;
+        }
+        write!(f, "{}: {} in `{}`", self.column, self.problem, self.pattern)
+    }
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f : &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Problem::Digit(c) => write!(f, "unexpected digit `{}`", c),
+            Problem::IndexOverflow(i) => write!(f, "index {} exceeds 255", i),
+            Problem::MissingBreakpoint => f.write_str("missing `=`"),
+            Problem::MissingSeparator => f.write_str("missing `,`"),
+            Problem::Number(ref s) => write!(f, "invalid subregion index `{}`", s),
+            Problem::SubregionIndices(n) => write!(f, "expected 2 subregion indices, found {}", n),
+            Problem::SubregionOutOfRange => f.write_str("subregion out of range"),
+            Problem::Unmarked => f.write_str("the subregion's opportunity carries no value"),
+        }
+    }
+}
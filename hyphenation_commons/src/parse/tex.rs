@@ -0,0 +1,267 @@
+//! Hyphenation files in their original TeX form.
+//!
+//! TeX hyphenation files declare their patterns and exceptions as arguments to
+//! the `\patterns` and `\hyphenation` primitives, interspersed with comments
+//! and other macros. Characters outside ASCII may be spelled out with `^^`
+//! escapes: `^^e9` or `^^^^00e9` for "é".
+//!
+//! ```text
+//! % hyph-xx.tex
+//! \patterns{
+//! .ab1 a2b 1ba
+//! }
+//! \hyphenation{
+//! ta-ble
+//! }
+//! ```
+//!
+//! Any other control sequence is disregarded, along with its braced argument.
+
+use std::error;
+use std::fmt;
+
+use crate::dictionary::{self as dict, Patterns, Standard};
+use crate::language::Language;
+use crate::normalization::Normalization;
+use crate::parse::{self, Listing, Parse};
+
+
+/// The patterns and exceptions declared in a TeX hyphenation file.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Source {
+    /// Patterns, in Knuth–Liang notation.
+    pub patterns :   Vec<String>,
+    /// Exceptions, hyphenated with `-`.
+    pub exceptions : Vec<String>,
+}
+
+impl Source {
+    /// Read the patterns and exceptions from the contents of a TeX file.
+    pub fn parse(tex : &str) -> Result<Self, Error> {
+        let chars = decode(tex)?;
+        let mut source = Source::default();
+        let mut i = 0;
+
+        while i < chars.len() {
+            let (c, line, _) = chars[i];
+            i += 1;
+            match c {
+                '\\' => {
+                    let start = i;
+                    while i < chars.len() && chars[i].0.is_ascii_alphabetic() {
+                        i += 1;
+                    }
+                    // A control symbol, such as `\%`, is a single character.
+                    if start == i {
+                        i += 1;
+                        continue;
+                    }
+                    let command : String = chars[start .. i].iter().map(|&(c, ..)| c).collect();
+                    while i < chars.len() && chars[i].0.is_whitespace() {
+                        i += 1;
+                    }
+                    if i == chars.len() || chars[i].0 != '{' {
+                        continue;
+                    }
+                    let (argument, next) = group(&chars, i, &command, line)?;
+                    i = next;
+                    match command.as_str() {
+                        "patterns" => {
+                            for word in words(argument) {
+                                let pattern = text(word);
+                                Patterns::tally(&pattern).map_err(|e| locate(e, word))?;
+                                source.patterns.push(pattern);
+                            }
+                        }
+                        "hyphenation" => {
+                            source.exceptions.extend(words(argument).map(text))
+                        }
+                        _ => (),
+                    }
+                }
+                '{' => i = group(&chars, i - 1, "", line)?.1,
+                _ => (),
+            }
+        }
+
+        Ok(source)
+    }
+
+    /// Build a `Standard` dictionary for the given language from the patterns
+    /// and exceptions, normalized to the given form and case-folded.
+    pub fn standard(&self,
+                    language : Language,
+                    normalization : Normalization)
+                    -> Result<Standard, Error> {
+        Listing { language,
+                  normalization,
+                  minima : None,
+                  patterns : &self.patterns,
+                  exceptions : &self.exceptions }.standard(|_, e| e)
+    }
+}
+
+/// Read a TeX hyphenation file into a `Standard` dictionary for the given
+/// language.
+pub fn standard(language : Language, tex : &str) -> Result<Standard, Error> {
+    Source::parse(tex)?.standard(language, Normalization::None)
+}
+
+
+/// A character, and the line and column where it was found.
+type Located = (char, usize, usize);
+
+/// Strip comments and resolve `^^` escapes, keeping track of the line where
+/// each character was found.
+fn decode(tex : &str) -> Result<Vec<Located>, Error> {
+    let mut decoded = vec![];
+    for (n, line) in tex.lines().enumerate() {
+        let line_number = n + 1;
+        let chars : Vec<char> = line.chars().collect();
+        let mut i = 0;
+        while i < chars.len() {
+            match chars[i] {
+                '%' => break,
+                '\\' if i + 1 < chars.len() => {
+                    decoded.push(('\\', line_number, i + 1));
+                    decoded.push((chars[i + 1], line_number, i + 2));
+                    i += 2;
+                }
+                '^' if chars.get(i + 1) == Some(&'^') => {
+                    let (c, length) =
+                        escape(&chars[i ..]).ok_or(Error::Escape { line : line_number })?;
+                    decoded.push((c, line_number, i + 1));
+                    i += length;
+                }
+                c => {
+                    decoded.push((c, line_number, i + 1));
+                    i += 1;
+                }
+            }
+        }
+        decoded.push(('\n', line_number, chars.len() + 1));
+    }
+
+    Ok(decoded)
+}
+
+/// Resolve the escape at the start of `chars`, returning the escaped character
+/// and the length of the escape sequence.
+fn escape(chars : &[char]) -> Option<(char, usize)> {
+    let hex = |digits : &[char]| -> Option<char> {
+        let is_hex = digits.iter()
+                           .all(|c| c.is_ascii_digit() || ('a' ..= 'f').contains(c));
+        if !is_hex {
+            return None;
+        }
+        let s : String = digits.iter().collect();
+        u32::from_str_radix(&s, 16).ok().and_then(char::from_u32)
+    };
+
+    if chars.len() >= 8 && chars[2 .. 4] == ['^', '^'] {
+        hex(&chars[4 .. 8]).map(|c| (c, 8))
+    } else if chars.len() >= 4 && hex(&chars[2 .. 4]).is_some() {
+        hex(&chars[2 .. 4]).map(|c| (c, 4))
+    } else {
+        // `^^c` shifts the character code of `c` by 64.
+        chars.get(2).and_then(|&c| {
+                        let code = c as u32;
+                        let shifted = if code < 64 { code + 64 } else { code - 64 };
+                        if code < 128 {
+                            char::from_u32(shifted).map(|c| (c, 3))
+                        } else {
+                            None
+                        }
+                    })
+    }
+}
+
+/// The contents of the braced group opening at `start`, and the index
+/// immediately following it.
+fn group<'c>(chars : &'c [Located],
+             start : usize,
+             command : &str,
+             line : usize)
+             -> Result<(&'c [Located], usize), Error> {
+    let mut depth = 0;
+    for (i, &(c, ..)) in chars.iter().enumerate().skip(start) {
+        match c {
+            '{' => depth += 1,
+            '}' => depth -= 1,
+            _ => (),
+        }
+        if depth == 0 {
+            return Ok((&chars[start + 1 .. i], i + 1));
+        }
+    }
+
+    Err(Error::Unterminated { command : command.to_owned(),
+                              line })
+}
+
+/// The whitespace-separated words in the group.
+fn words(group : &[Located]) -> impl Iterator<Item = &[Located]> {
+    group.split(|&(c, ..)| c.is_whitespace())
+         .filter(|word| !word.is_empty())
+}
+
+fn text(word : &[Located]) -> String { word.iter().map(|&(c, ..)| c).collect() }
+
+/// Attach the line of a malformed word to its error, and count the column from
+/// the start of the line, escapes and all.
+fn locate(err : parse::Error, word : &[Located]) -> parse::Error {
+    let k = err.column - 1;
+    let last = word.len() - 1;
+    let (_, line, column) = word[k.min(last)];
+    // Problems past the end of the word lie as far past its last character.
+    parse::Error { column : column + k.saturating_sub(last),
+                   ..err.at_line(line) }
+}
+
+
+/// Failure modes of TeX hyphenation file parsing.
+#[derive(Debug)]
+pub enum Error {
+    /// A `^^` escape did not denote a valid character.
+    Escape { line : usize },
+    /// A braced group was not closed.
+    Unterminated { command : String, line : usize },
+    /// A pattern or exception is malformed.
+    Parse(parse::Error),
+    /// The dictionary's internal trie could not be built.
+    Trie(dict::Error),
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match *self {
+            Error::Parse(ref e) => Some(e),
+            Error::Trie(ref e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f : &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::Escape { line } => write!(f, "line {}: invalid `^^` escape", line),
+            Error::Unterminated { ref command, line } if command.is_empty() => {
+                write!(f, "line {}: unterminated group", line)
+            }
+            Error::Unterminated { ref command, line } => {
+                write!(f, "line {}: unterminated argument to `\\{}`", line, command)
+            }
+            Error::Parse(ref e) => e.fmt(f),
+            Error::Trie(ref e) => e.fmt(f),
+        }
+    }
+}
+
+impl From<parse::Error> for Error {
+    fn from(err : parse::Error) -> Error { Error::Parse(err) }
+}
+
+impl From<dict::Error> for Error {
+    fn from(err : dict::Error) -> Error { Error::Trie(err) }
+}
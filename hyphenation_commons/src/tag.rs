@@ -0,0 +1,322 @@
+//! Parsing of BCP 47 language tags, and their matching to supported languages.
+//!
+//! A [`Tag`] is parsed into its subtags, regardless of their case and of
+//! whether they are separated by hyphens or underscores. [`Language::from_tag`]
+//! then finds the supported language that best serves it, reporting the
+//! subtags which were of no consequence to the choice:
+//!
+//! ```
+//! use hyphenation_commons::Language;
+//!
+//! let resolved = Language::from_tag("sr_latn_RS").unwrap();
+//! assert_eq!(resolved.language, Language::SerbocroatianLatin);
+//! assert_eq!(resolved.ignored, vec!["RS"]);
+//! ```
+//!
+//! Languages are matched on their primary subtag, then on their script,
+//! region, variants and private use subtags. Where a tag does not specify
+//! the variety of a language, the most common is chosen: `en` is served by
+//! `en-us`, `de` by `de-1996`, and `el` by `el-monoton`. Subtags that
+//! contradict every variety of the language, as the script of `zh-Hans`,
+//! fail the match.
+//!
+//! [`Tag`]: struct.Tag.html
+//! [`Language::from_tag`]: ../enum.Language.html#method.from_tag
+
+use std::error;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::language::{Language, LANGUAGES};
+
+
+/// A BCP 47 language tag, with its subtags in canonical case.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Tag {
+    /// The primary language subtag, such as `en`, in lowercase.
+    pub language :    String,
+    /// Extended language subtags, in lowercase.
+    pub extlang :     Vec<String>,
+    /// The script subtag, such as `Latn`, in title case.
+    pub script :      Option<String>,
+    /// The region subtag, such as `US` or `419`, in uppercase.
+    pub region :      Option<String>,
+    /// Variant subtags, such as `1996`, in lowercase.
+    pub variants :    Vec<String>,
+    /// Extensions, each with its singleton, as `u-co-phonebk`, in lowercase.
+    pub extensions :  Vec<String>,
+    /// Private use subtags, following `x`, in lowercase.
+    pub private_use : Vec<String>,
+}
+
+impl Tag {
+    /// Parse a tag, regardless of case, and of whether its subtags are
+    /// separated by hyphens or underscores.
+    pub fn parse(tag : &str) -> Result<Tag, Error> {
+        let tag = tag.trim();
+        if tag.is_empty() {
+            return Err(Error::Empty);
+        }
+        let mut subtags = tag.split(['-', '_']).peekable();
+        if let Some(s) = subtags.clone().find(|s| !is_subtag(s)) {
+            return Err(Error::Malformed(s.to_owned()));
+        }
+
+        let mut parsed = Tag::default();
+        let language = subtags.next().unwrap_or_default();
+        if language.eq_ignore_ascii_case("x") || !is_language(language) {
+            return Err(Error::Language(language.to_owned()));
+        }
+        parsed.language = language.to_ascii_lowercase();
+
+        if language.len() <= 3 {
+            while let Some(s) = subtags.next_if(|s| is_alpha(s, 3 ..= 3)) {
+                parsed.extlang.push(s.to_ascii_lowercase());
+            }
+        }
+        if let Some(s) = subtags.next_if(|s| is_alpha(s, 4 ..= 4)) {
+            let mut script = s.to_ascii_lowercase();
+            script[.. 1].make_ascii_uppercase();
+            parsed.script = Some(script);
+        }
+        if let Some(s) = subtags.next_if(|s| is_region(s)) {
+            parsed.region = Some(s.to_ascii_uppercase());
+        }
+        while let Some(s) = subtags.next_if(|s| is_variant(s)) {
+            parsed.variants.push(s.to_ascii_lowercase());
+        }
+
+        while let Some(singleton) = subtags.next() {
+            let singleton = singleton.to_ascii_lowercase();
+            let private = singleton == "x";
+            if singleton.len() != 1 {
+                return Err(Error::Malformed(singleton));
+            }
+
+            let mut extension = vec![];
+            while let Some(s) = subtags.next_if(|s| private || s.len() > 1) {
+                extension.push(s.to_ascii_lowercase());
+            }
+            if private {
+                parsed.private_use = extension;
+            } else if extension.is_empty() {
+                return Err(Error::Malformed(singleton));
+            } else {
+                parsed.extensions
+                      .push([singleton, extension.join("-")].join("-"));
+            }
+        }
+        Ok(parsed)
+    }
+
+    /// The subtags of the tag, in order and in canonical case.
+    pub fn subtags(&self) -> Vec<String> {
+        let mut subtags = vec![self.language.clone()];
+        subtags.extend(self.extlang.iter().cloned());
+        subtags.extend(self.script.iter().cloned());
+        subtags.extend(self.region.iter().cloned());
+        subtags.extend(self.variants.iter().cloned());
+        subtags.extend(self.extensions.iter().cloned());
+        if !self.private_use.is_empty() {
+            subtags.push("x".to_owned());
+            subtags.extend(self.private_use.iter().cloned());
+        }
+        subtags
+    }
+}
+
+impl FromStr for Tag {
+    type Err = Error;
+
+    fn from_str(s : &str) -> Result<Self, Self::Err> { Tag::parse(s) }
+}
+
+impl fmt::Display for Tag {
+    fn fmt(&self, f : &mut fmt::Formatter) -> fmt::Result { f.write_str(&self.subtags().join("-")) }
+}
+
+
+/// The language chosen for a tag.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Resolved {
+    pub language : Language,
+    /// The subtags of the tag that played no part in the choice, in
+    /// canonical case. Extensions are given whole, as `u-co-phonebk`.
+    pub ignored :  Vec<String>,
+}
+
+impl Language {
+    /// Find the supported language that best serves a BCP 47 tag, as
+    /// described in the [`tag`] module.
+    ///
+    /// [`tag`]: tag/index.html
+    pub fn from_tag(tag : &str) -> Result<Resolved, Error> { Tag::parse(tag)?.resolve() }
+}
+
+impl Tag {
+    /// Find the supported language that best serves the tag, as described in
+    /// the [`tag`] module.
+    ///
+    /// [`tag`]: index.html
+    pub fn resolve(&self) -> Result<Resolved, Error> {
+        let (language, implied) = match (self.language.as_str(), self.script.as_deref()) {
+            ("sr", Some("Latn")) => ("sh", None),
+            ("no", _) => ("nb", None),
+            ("ku", _) => ("kmr", None),
+            ("am", _) | ("ti", _) => ("mul", Some("Ethi")),
+            ("sr", _) | ("mn", _) => (self.language.as_str(), Some("Cyrl")),
+            ("sh", _) => ("sh", Some("Latn")),
+            (l, _) => (l, None),
+        };
+
+        let varieties : Vec<(Language, Tag)> =
+            LANGUAGES.iter()
+                     .map(|&l| (l, Tag::parse(l.code()).expect("malformed language code")))
+                     .filter(|(_, t)| t.language == language)
+                     .collect();
+        if varieties.is_empty() {
+            return Err(Error::Language(self.language.clone()));
+        }
+
+        // Varieties are ranked by the subtags they share with the tag, then by
+        // whether they are the most common, then by their generality.
+        let best = varieties.iter()
+                            .filter(|(_, v)| self.admits(v, implied))
+                            .max_by_key(|&&(l, ref v)| {
+                                (self.shared(v), is_common(l), std::cmp::Reverse(specificity(v)))
+                            });
+        let (language, variety) = match best {
+            Some((l, v)) => (*l, v),
+            None => {
+                return Err(Error::Variety { tag :       self.to_string(),
+                                            supported : varieties.iter().map(|&(l, _)| l).collect(), })
+            }
+        };
+
+        let mut ignored = self.extlang.clone();
+        ignored.extend(self.script
+                           .iter()
+                           .filter(|&s| variety.script.as_ref() != Some(s))
+                           .cloned());
+        ignored.extend(self.region
+                           .iter()
+                           .filter(|&r| variety.region.as_ref() != Some(r))
+                           .cloned());
+        ignored.extend(self.variants
+                           .iter()
+                           .filter(|v| !variety.variants.contains(v))
+                           .cloned());
+        ignored.extend(self.extensions.iter().cloned());
+        ignored.extend(self.private_use
+                           .iter()
+                           .filter(|p| !variety.private_use.contains(p))
+                           .cloned());
+
+        Ok(Resolved { language, ignored })
+    }
+
+    /// Whether the variety of a language can serve the tag: the script and
+    /// private use subtags it specifies must be found in the tag, or implied
+    /// by it, and so must its variants, if the tag has any. Regions are only
+    /// a preference.
+    fn admits(&self, variety : &Tag, implied_script : Option<&str>) -> bool {
+        let script = match (&variety.script, &self.script) {
+            (Some(v), Some(s)) => v == s,
+            (Some(v), None) => implied_script == Some(v.as_str()),
+            (None, _) => true,
+        };
+        let variants =
+            self.variants.is_empty() || variety.variants.iter().all(|v| self.variants.contains(v));
+        let private_use = variety.private_use
+                                 .iter()
+                                 .all(|p| self.private_use.contains(p));
+
+        script && variants && private_use
+    }
+
+    /// The number of subtags that the variety of a language shares with the
+    /// tag.
+    fn shared(&self, variety : &Tag) -> usize {
+        let script = variety.script.is_some() && variety.script == self.script;
+        let region = variety.region.is_some() && variety.region == self.region;
+        let variants = variety.variants
+                              .iter()
+                              .filter(|v| self.variants.contains(v))
+                              .count();
+        let private_use = variety.private_use
+                                 .iter()
+                                 .filter(|p| self.private_use.contains(p))
+                                 .count();
+
+        script as usize + region as usize + variants + private_use
+    }
+}
+
+/// Whether the language is the most common variety of its own, among those
+/// supported.
+fn is_common(language : Language) -> bool {
+    matches!(language,
+             Language::EnglishUS | Language::German1996 | Language::GreekMono)
+}
+
+/// The number of subtags beyond the primary language.
+fn specificity(tag : &Tag) -> usize { tag.subtags().len() - 1 }
+
+fn is_subtag(s : &str) -> bool {
+    (1 ..= 8).contains(&s.len()) && s.bytes().all(|b| b.is_ascii_alphanumeric())
+}
+
+fn is_alpha(s : &str, lengths : std::ops::RangeInclusive<usize>) -> bool {
+    lengths.contains(&s.len()) && s.bytes().all(|b| b.is_ascii_alphabetic())
+}
+
+fn is_language(s : &str) -> bool { is_alpha(s, 2 ..= 3) || is_alpha(s, 5 ..= 8) }
+
+fn is_region(s : &str) -> bool {
+    is_alpha(s, 2 ..= 2) || (s.len() == 3 && s.bytes().all(|b| b.is_ascii_digit()))
+}
+
+fn is_variant(s : &str) -> bool {
+    (5 ..= 8).contains(&s.len()) || (s.len() == 4 && s.as_bytes()[0].is_ascii_digit())
+}
+
+
+/// Failure modes of tag parsing and matching.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Error {
+    /// The tag is empty.
+    Empty,
+    /// The subtag is not well-formed, or out of place.
+    Malformed(String),
+    /// No supported language has the primary language subtag.
+    Language(String),
+    /// The tag contradicts every supported variety of its language.
+    Variety {
+        tag :       String,
+        supported : Vec<Language>,
+    },
+}
+
+impl error::Error for Error {}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f : &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::Empty => f.write_str("the language tag is empty"),
+            Error::Malformed(ref s) if s.is_empty() => {
+                f.write_str("the language tag has an empty subtag")
+            }
+            Error::Malformed(ref s) => {
+                write!(f, "`{}` is not a well-formed subtag in its position", s)
+            }
+            Error::Language(ref l) => write!(f, "no supported language for `{}`", l),
+            Error::Variety { ref tag, ref supported } => {
+                let codes : Vec<_> = supported.iter().map(|l| l.code()).collect();
+                write!(f,
+                       "`{}` matches no supported variety of its language ({})",
+                       tag,
+                       codes.join(", "))
+            }
+        }
+    }
+}
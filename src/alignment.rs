@@ -0,0 +1,30 @@
+/*!
+Realignment of breaks with the word as it was given
+
+Words are normalized and case-folded before hyphenation, and either may
+change the length of parts of the word, and thus the indices of its breaks.
+Each step of preparation keeps track of the spans of the word that it
+altered, so that breaks found in the prepared word can be realigned with the
+word as it was before. Breaks within an altered span are moved to its start,
+lest they split a character of the original.
+*/
+
+/// A span of an altered word that differs from the original.
+#[derive(Copy, Clone, Debug)]
+pub struct Span {
+    /// The start of the span in the altered word.
+    pub start :    usize,
+    /// The end of the span in the altered word.
+    pub end :      usize,
+    /// The range of the span in the original word.
+    pub original : (usize, usize),
+}
+
+/// The opportunity `i`, index-corrected for use in the original string.
+pub fn realign(i : usize, spans : &[Span]) -> usize {
+    match spans.iter().rev().find(|span| i > span.start) {
+        None => i,
+        Some(span) if i >= span.end => i - span.end + span.original.1,
+        Some(span) => span.original.0,
+    }
+}
@@ -0,0 +1,349 @@
+/*! `hyphenate`: hyphenate text from files or standard input.
+
+```text
+$ echo "ever-burning sulphur unconsumed" | hyphenate --lang en-us --dict en-us.standard.bincode
+ever-burn-ing sul-phur un-con-sumed
+```
+*/
+
+use std::env;
+use std::error;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufRead, Write};
+use std::path::{Path, PathBuf};
+use std::process;
+
+use hyphenation::parse::{self, Parse};
+use hyphenation::{load, registry, Hyphenator, Iter, Language, Load, Standard, TextHyphenator};
+use hyphenation_commons::caseless;
+use hyphenation_commons::dictionary::Exceptions;
+
+
+const USAGE : &str = "\
+Usage: hyphenate [OPTIONS] [FILE]...
+
+Hyphenate the text in each FILE, or standard input if none is given (or if
+FILE is `-`), and write it to standard output.
+
+Options:
+  -l, --lang TAG          the language of the text, as a BCP 47 tag (`en-us`)
+  -d, --dict PATH         load the dictionary at PATH rather than the embedded
+                          dictionary for the language
+  -o, --output MODE       how to show breaks:
+                            mark   insert a visible mark (default)
+                            soft   insert soft hyphens (U+00AD)
+                            words  print each word on its own line, marked
+      --mark STRING       the visible mark (default: `-`)
+      --minima LEFT,RIGHT the minimum number of characters before the first
+                          and after the last break of a word
+      --min-length N      leave words shorter than N characters unbroken
+      --max-breaks N      break each word at no more than N places
+  -e, --exceptions PATH   a file of words hyphenated with `-`, one per line,
+                          which take precedence over the dictionary
+  -h, --help              print this help
+";
+
+
+/// The ways in which breaks can be shown.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Output {
+    Mark,
+    Soft,
+    Words,
+}
+
+#[derive(Clone, Debug)]
+struct Options {
+    language :   Option<Language>,
+    dictionary : Option<PathBuf>,
+    output :     Output,
+    mark :       String,
+    minima :     Option<(usize, usize)>,
+    min_length : Option<usize>,
+    max_breaks : Option<usize>,
+    exceptions : Vec<PathBuf>,
+    inputs :     Vec<PathBuf>,
+}
+
+impl Options {
+    fn from_args<I>(args : I) -> Result<Option<Self>, Error>
+        where I : IntoIterator<Item = String>
+    {
+        let mut options = Options { language :   None,
+                                    dictionary : None,
+                                    output :     Output::Mark,
+                                    mark :       "-".to_owned(),
+                                    minima :     None,
+                                    min_length : None,
+                                    max_breaks : None,
+                                    exceptions : vec![],
+                                    inputs :     vec![], };
+
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            // Long options may be given their value after an `=`.
+            let (flag, inline) = match arg.find('=') {
+                Some(i) if arg.starts_with("--") => (&arg[.. i], Some(arg[i + 1 ..].to_owned())),
+                _ => (arg.as_str(), None),
+            };
+            let mut value = || {
+                inline.clone()
+                      .or_else(|| args.next())
+                      .ok_or_else(|| Error::Usage(format!("`{}` requires a value", flag)))
+            };
+
+            match flag {
+                "-h" | "--help" => return Ok(None),
+                "-l" | "--lang" => {
+                    let tag = value()?;
+                    let language = registry::resolve(&tag).map_err(|e| {
+                                       Error::Usage(format!("unknown language `{}`: {}", tag, e))
+                                   })?;
+                    options.language = Some(language);
+                }
+                "-d" | "--dict" => options.dictionary = Some(value()?.into()),
+                "-o" | "--output" => {
+                    options.output = match value()?.as_str() {
+                        "mark" => Output::Mark,
+                        "soft" => Output::Soft,
+                        "words" => Output::Words,
+                        mode => {
+                            return Err(Error::Usage(format!("unknown output mode `{}`", mode)))
+                        }
+                    }
+                }
+                "--mark" => options.mark = value()?,
+                "--minima" => {
+                    let minima = value()?;
+                    let parsed = minima.split_once(',')
+                                       .and_then(|(l, r)| {
+                                           Some((l.trim().parse().ok()?, r.trim().parse().ok()?))
+                                       });
+                    let minima = parsed.ok_or_else(|| {
+                                           Error::Usage(format!("invalid minima `{}`, \
+                                                                 expected LEFT,RIGHT",
+                                                                minima))
+                                       })?;
+                    options.minima = Some(minima);
+                }
+                "--min-length" => options.min_length = Some(count(flag, &value()?)?),
+                "--max-breaks" => options.max_breaks = Some(count(flag, &value()?)?),
+                "-e" | "--exceptions" => options.exceptions.push(value()?.into()),
+                "--" => options.inputs.extend(args.by_ref().map(PathBuf::from)),
+                "-" => options.inputs.push(arg.into()),
+                _ if flag.starts_with('-') => {
+                    return Err(Error::Usage(format!("unknown option `{}`", flag)))
+                }
+                _ => options.inputs.push(arg.into()),
+            }
+        }
+
+        if options.language.is_none() && options.dictionary.is_none() {
+            return Err(Error::Usage("a language (`--lang`) or dictionary (`--dict`) is required"
+                                        .to_owned()));
+        }
+        if options.inputs.is_empty() {
+            options.inputs.push("-".into());
+        }
+
+        Ok(Some(options))
+    }
+
+    /// Load the dictionary, and amend it with the requested limits and
+    /// exceptions.
+    fn dictionary(&self) -> Result<Standard, Error> {
+        let mut dictionary = match (&self.dictionary, self.language) {
+            (Some(path), Some(language)) => Standard::from_path(language, path)?,
+            (Some(path), None) => {
+                Standard::any_from_reader(&mut io::BufReader::new(File::open(path)?))?
+            }
+            (None, Some(language)) => embedded(language)?,
+            (None, None) => unreachable!(),
+        };
+
+        if let Some(minima) = self.minima {
+            dictionary.options.minima = minima;
+        }
+        if let Some(length) = self.min_length {
+            dictionary.options.min_length = length;
+        }
+        if let Some(count) = self.max_breaks {
+            dictionary.options.max_breaks = Some(count);
+        }
+        for path in &self.exceptions {
+            read_exceptions(path, &mut dictionary)?;
+        }
+
+        Ok(dictionary)
+    }
+}
+
+
+/// Parse the numeric value of an option.
+fn count(flag : &str, value : &str) -> Result<usize, Error> {
+    value.trim()
+         .parse()
+         .map_err(|_| Error::Usage(format!("invalid count `{}` for `{}`", value, flag)))
+}
+
+
+#[cfg(any(feature = "embed_all", feature = "embed_en-us"))]
+fn embedded(language : Language) -> Result<Standard, Error> {
+    Ok(Standard::from_embedded(language)?)
+}
+
+#[cfg(not(any(feature = "embed_all", feature = "embed_en-us")))]
+fn embedded(_ : Language) -> Result<Standard, Error> {
+    Err(Error::Usage("no dictionaries are embedded in this build; \
+                      pass one with `--dict`"
+                                             .to_owned()))
+}
+
+/// Read words hyphenated with `-`, one per line, into the exceptions of the
+/// dictionary, folded as its patterns. Blank lines and lines starting with `%`
+/// are ignored.
+fn read_exceptions(path : &Path, dictionary : &mut Standard) -> Result<(), Error> {
+    let (form, folding) = (dictionary.normalization(), dictionary.language().folding());
+    let reader = io::BufReader::new(File::open(path)?);
+    for (n, line) in reader.lines().enumerate() {
+        let line = line?;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('%') {
+            continue;
+        }
+        let (word, tally) = Exceptions::pair(line, |s| caseless(s, form, folding))
+                                .map_err(|e| e.in_file(path).at_line(n + 1))?;
+        dictionary.exceptions.0.insert(word, tally);
+    }
+
+    Ok(())
+}
+
+
+fn hyphenate<R, W>(dictionary : &Standard,
+                   options : &Options,
+                   input : R,
+                   output : &mut W)
+                   -> io::Result<()>
+    where R : BufRead,
+          W : Write
+{
+    let mark = match options.output {
+        Output::Soft => "\u{ad}",
+        _ => options.mark.as_str(),
+    };
+    let text = TextHyphenator::new(dictionary);
+
+    for line in input.lines() {
+        let line = line?;
+        match options.output {
+            Output::Mark | Output::Soft => {
+                let hyphenated = text.hyphenate(&line);
+                for segment in hyphenated.segments_with_mark(mark) {
+                    output.write_all(segment.as_bytes())?;
+                }
+                writeln!(output)?;
+            }
+            Output::Words => {
+                for (_, word) in text.words(&line) {
+                    let hyphenated = dictionary.hyphenate(word);
+                    let marked : String = hyphenated.segments_with_mark(mark).collect();
+                    writeln!(output, "{}", marked)?;
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn run(options : &Options) -> Result<(), Error> {
+    let dictionary = options.dictionary()?;
+    let stdout = io::stdout();
+    let mut output = io::BufWriter::new(stdout.lock());
+
+    for path in &options.inputs {
+        if path.as_os_str() == "-" {
+            let stdin = io::stdin();
+            hyphenate(&dictionary, options, stdin.lock(), &mut output)?;
+        } else {
+            let file = File::open(path).map_err(|e| Error::Input(path.clone(), e))?;
+            hyphenate(&dictionary, options, io::BufReader::new(file), &mut output)?;
+        }
+    }
+
+    output.flush()?;
+    Ok(())
+}
+
+fn main() {
+    let result = Options::from_args(env::args().skip(1)).and_then(|options| match options {
+                                                            Some(options) => run(&options),
+                                                            None => {
+                                                                print!("{}", USAGE);
+                                                                Ok(())
+                                                            }
+                                                        });
+
+    match result {
+        Ok(()) => (),
+        // Downstream consumers such as `head` may stop reading early.
+        Err(Error::IO(ref e)) if e.kind() == io::ErrorKind::BrokenPipe => (),
+        Err(e @ Error::Usage(_)) => {
+            eprintln!("hyphenate: {}\nTry `hyphenate --help` for more information.", e);
+            process::exit(2);
+        }
+        Err(e) => {
+            eprintln!("hyphenate: {}", e);
+            process::exit(1);
+        }
+    }
+}
+
+
+/// Failure modes of the command line.
+#[derive(Debug)]
+enum Error {
+    Input(PathBuf, io::Error),
+    IO(io::Error),
+    Load(load::Error),
+    Parse(parse::Error),
+    Usage(String),
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match *self {
+            Error::Input(_, ref e) => Some(e),
+            Error::IO(ref e) => Some(e),
+            Error::Load(ref e) => Some(e),
+            Error::Parse(ref e) => Some(e),
+            Error::Usage(_) => None,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f : &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::Input(ref path, ref e) => write!(f, "{}: {}", path.display(), e),
+            Error::IO(ref e) => e.fmt(f),
+            Error::Load(ref e) => e.fmt(f),
+            Error::Parse(ref e) => e.fmt(f),
+            Error::Usage(ref message) => f.write_str(message),
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(err : io::Error) -> Error { Error::IO(err) }
+}
+
+impl From<load::Error> for Error {
+    fn from(err : load::Error) -> Error { Error::Load(err) }
+}
+
+impl From<parse::Error> for Error {
+    fn from(err : parse::Error) -> Error { Error::Parse(err) }
+}
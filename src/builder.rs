@@ -0,0 +1,281 @@
+/*! # Building dictionaries at runtime
+
+The dictionaries bundled with `hyphenation` are built from the patterns in
+the `patterns` folder, but they are by no means the only ones available: any
+set of Knuth–Liang patterns can be compiled into a dictionary at runtime with
+a [`DictionaryBuilder`].
+
+```
+use hyphenation::builder::DictionaryBuilder;
+use hyphenation::{Hyphenator, Language};
+
+let dictionary = DictionaryBuilder::new(Language::EnglishUS)
+    .patterns(vec![".ach4", "hy3ph", "he2n", "hena4", "hen5at", "1na", "n2at"])
+    .exceptions(vec!["ta-ble"])
+    .minima(2, 3)
+    .standard()?;
+
+assert_eq!(dictionary.hyphenate("hyphenation").breaks, &[2, 6]);
+assert_eq!(dictionary.hyphenate("table").breaks, &[2]);
+# Ok::<(), hyphenation::builder::Error>(())
+```
+
+Patterns and exceptions may also be read from files, or any other source that
+implements `BufRead`, in the whitespace-separated form of the files in
+`patterns`:
+
+```ignore
+let dictionary = DictionaryBuilder::new(Language::EnglishUS)
+    .patterns_from_path("hyph-xx.pat.txt")?
+    .standard()?;
+```
+
+Malformed patterns are reported with their file, line and column, rather than
+causing a panic. Patterns and exceptions are case-folded as suits the language
+of the dictionary, so that they may be given in any case.
+
+Once built, dictionaries can be serialized with the [`Store`] trait, and later
+retrieved with [`Load`] like any other.
+
+
+[`DictionaryBuilder`]: struct.DictionaryBuilder.html
+[`Store`]: ../load/trait.Store.html
+[`Load`]: ../load/trait.Load.html
+*/
+
+use std::error;
+use std::fmt;
+use std::fs::File;
+use std::io;
+use std::path::Path;
+use std::sync::Arc;
+
+use hyphenation_commons::dictionary::{self as dict, extended as ext, Standard};
+use hyphenation_commons::parse;
+use hyphenation_commons::{HyphenationOptions, Language};
+pub use hyphenation_commons::Normalization;
+
+
+/// A builder for hyphenation dictionaries, gathering patterns and exceptions
+/// from arbitrary sources.
+#[derive(Clone, Debug)]
+pub struct DictionaryBuilder {
+    language :      Language,
+    options :       Option<HyphenationOptions>,
+    normalization : Normalization,
+    patterns :      Vec<Entry>,
+    exceptions :    Vec<Entry>,
+}
+
+impl DictionaryBuilder {
+    /// Start building a dictionary for the given language, with no patterns,
+    /// no exceptions, and no normalization.
+    pub fn new(language : Language) -> Self {
+        DictionaryBuilder { language,
+                            options : None,
+                            normalization : Normalization::None,
+                            patterns : vec![],
+                            exceptions : vec![] }
+    }
+
+    /// Set the minimum number of graphemes to leave before the first and
+    /// after the last break. If unset, the language's own minima are used.
+    pub fn minima(mut self, left : usize, right : usize) -> Self {
+        let options = self.options.unwrap_or_else(|| self.language.into());
+        self.options = Some(options.minima(left, right));
+        self
+    }
+
+    /// Set the options with which the dictionary hyphenates words, in place
+    /// of any minima set before. If unset, those of the language are used.
+    pub fn options(mut self, options : HyphenationOptions) -> Self {
+        self.options = Some(options);
+        self
+    }
+
+    /// Normalize patterns and exceptions to the given form. The form is
+    /// recorded in the dictionary, and words submitted for hyphenation are
+    /// normalized likewise.
+    pub fn normalization(mut self, form : Normalization) -> Self {
+        self.normalization = form;
+        self
+    }
+
+    /// Add patterns in Knuth–Liang notation, such as `hy3ph`, or, for
+    /// extended dictionaries, in the notation described by Németh.
+    pub fn patterns<I, S>(mut self, patterns : I) -> Self
+        where I : IntoIterator<Item = S>,
+              S : Into<String>
+    {
+        self.patterns.extend(patterns.into_iter().map(Entry::new));
+        self
+    }
+
+    /// Add exceptions, hyphenated with `-`, such as `ta-ble`.
+    pub fn exceptions<I, S>(mut self, exceptions : I) -> Self
+        where I : IntoIterator<Item = S>,
+              S : Into<String>
+    {
+        self.exceptions
+            .extend(exceptions.into_iter().map(Entry::new));
+        self
+    }
+
+    /// Read whitespace-separated patterns from the reader.
+    pub fn patterns_from_reader<R>(mut self, reader : R) -> Result<Self, Error>
+        where R : io::BufRead
+    {
+        self.patterns.extend(entries(reader, None)?);
+        Ok(self)
+    }
+
+    /// Read whitespace-separated exceptions from the reader.
+    pub fn exceptions_from_reader<R>(mut self, reader : R) -> Result<Self, Error>
+        where R : io::BufRead
+    {
+        self.exceptions.extend(entries(reader, None)?);
+        Ok(self)
+    }
+
+    /// Read whitespace-separated patterns from the file at the given path.
+    /// Malformed patterns will be reported along with the path.
+    pub fn patterns_from_path<P>(mut self, path : P) -> Result<Self, Error>
+        where P : AsRef<Path>
+    {
+        let reader = io::BufReader::new(File::open(&path)?);
+        self.patterns
+            .extend(entries(reader, Some(path.as_ref().into()))?);
+        Ok(self)
+    }
+
+    /// Read whitespace-separated exceptions from the file at the given path.
+    pub fn exceptions_from_path<P>(mut self, path : P) -> Result<Self, Error>
+        where P : AsRef<Path>
+    {
+        let reader = io::BufReader::new(File::open(&path)?);
+        self.exceptions
+            .extend(entries(reader, Some(path.as_ref().into()))?);
+        Ok(self)
+    }
+
+    /// Build a `Standard` dictionary. Patterns in extended notation are
+    /// reduced to their standard portion.
+    pub fn standard(&self) -> Result<Standard, Error> {
+        let mut dict = self.listing().standard::<Error, _>(Entry::locate)?;
+        dict.options = self.options.unwrap_or(dict.options);
+        Ok(dict)
+    }
+
+    /// Build an `Extended` dictionary.
+    pub fn extended(&self) -> Result<ext::Extended, Error> {
+        let mut dict = self.listing().extended::<Error, _>(Entry::locate)?;
+        dict.options = self.options.unwrap_or(dict.options);
+        Ok(dict)
+    }
+
+    fn listing(&self) -> parse::Listing<'_, Entry> {
+        parse::Listing { language :      self.language,
+                         normalization : self.normalization,
+                         minima :        self.options.map(|o| o.minima),
+                         patterns :      &self.patterns,
+                         exceptions :    &self.exceptions, }
+    }
+}
+
+
+/// A pattern or exception, and where it was found.
+#[derive(Clone, Debug)]
+struct Entry {
+    text :   String,
+    file :   Option<Arc<Path>>,
+    line :   Option<usize>,
+    /// The column of the entry's first character within its line.
+    column : Option<usize>,
+}
+
+impl AsRef<str> for Entry {
+    fn as_ref(&self) -> &str { &self.text }
+}
+
+impl Entry {
+    fn new<S : Into<String>>(text : S) -> Self {
+        Entry { text :   text.into(),
+                file :   None,
+                line :   None,
+                column : None, }
+    }
+
+    /// Attach the entry's origin to a parsing error.
+    fn locate(&self, mut err : parse::Error) -> parse::Error {
+        err.file = self.file.as_ref().map(|f| f.to_path_buf());
+        err.line = self.line;
+        match self.column {
+            Some(column) => err.offset(column - 1),
+            None => err,
+        }
+    }
+}
+
+fn entries<R>(reader : R, file : Option<Arc<Path>>) -> Result<Vec<Entry>, Error>
+    where R : io::BufRead
+{
+    let mut entries = vec![];
+    for (n, line) in reader.lines().enumerate() {
+        let mut column = 1;
+        for word in line?.split(char::is_whitespace) {
+            if !word.is_empty() {
+                entries.push(Entry { text :   word.to_owned(),
+                                     file :   file.clone(),
+                                     line :   Some(n + 1),
+                                     column : Some(column), });
+            }
+            column += word.chars().count() + 1;
+        }
+    }
+
+    Ok(entries)
+}
+
+
+/// Failure modes of dictionary building.
+#[derive(Debug)]
+pub enum Error {
+    /// Patterns or exceptions could not be read.
+    IO(io::Error),
+    /// A pattern or exception is malformed.
+    Parse(parse::Error),
+    /// The dictionary's internal trie could not be built.
+    Trie(dict::Error),
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match *self {
+            Error::IO(ref e) => Some(e),
+            Error::Parse(ref e) => Some(e),
+            Error::Trie(ref e) => Some(e),
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f : &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::IO(ref e) => e.fmt(f),
+            Error::Parse(ref e) => e.fmt(f),
+            Error::Trie(ref e) => e.fmt(f),
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(err : io::Error) -> Error { Error::IO(err) }
+}
+
+impl From<parse::Error> for Error {
+    fn from(err : parse::Error) -> Error { Error::Parse(err) }
+}
+
+impl From<dict::Error> for Error {
+    fn from(err : dict::Error) -> Error { Error::Trie(err) }
+}
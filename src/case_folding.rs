@@ -1,98 +1,47 @@
-/*! Special casing for the purpose of hyphenation
+/*! Case folding of words for hyphenation
 
-Implemented here is a "refolding" pass which covers complementary case
-mappings (rarely) required for the correct hyphenation of uppercase or
-mixed-case words.
+Patterns and exceptions are case-folded when dictionaries are built, as
+described by [`Folding`], and words are folded likewise before hyphenation,
+such that uppercase and mixed-case words are hyphenated exactly as their
+lowercase forms.
 
-Patterns maintained by the TeX project are not generated in strict accordance
-with Unicode case folding; rather, they include multiple concrete sequences
-at the discretion of the pattern developer, under the assumption that words
-may be lowercased with varying degrees of Unicode awareness.
+Folding may change the length of a word, and thus the indices of its breaks:
+"ẞ" folds to "ss", and "İ" to "i" in all but Lithuanian. Every
+character that folds to a sequence of different length, or to several
+characters, is kept as a span of its own, so that breaks found in the folded
+word can be realigned with the original. Thus, a break between the two
+letters of "ss" is moved before the "ẞ" which they fold.
 
-Although the approach works quite well when matching against `str`, two
-discrepancies remain:
 
-- Firstly, folding may shift character boundaries, invalidating the indices
-of opportunities found by our dictionaries, which are byte-based and have
-no notion of `char`. Thus, any opportunity found in a folded word must be
-mapped back to its correct position in the original, unfolded word.
-- Secondly, patterns may not account for all concrete sequences that occur
-when using `str::to_lowercase` as a loose folding pass.
-
-A zealous solution would be to adopt proper caseless matching, and pre-fold
-the bundled patterns. Presently, however, we rely on an ad-hoc remedy based
-on supplementary case mappings.
-
-
-# Supplementary case mappings
-
-| uppercase            | lowercase            | refold
-|----------------------|----------------------|----------------
-|'İ' \u{130}, length 2 | "i\u{307}", length 3 | "i", length 1
-
-The Turkish `İ` in its recomposed form is – to the author's knowledge –
-the only codepoint which changes size when lowercased without normalization
-or tailoring. It is thus necessary to fold it regardless of language or
-context, because the equivalence-preserving lowercase "i\u{307}" not only
-disrupts pattern matching – be it byte-based or char-based – but also
-shifts and invalidates any opportunity arising after it.
+[`Folding`]: ../../hyphenation_commons/enum.Folding.html
 */
 
 use std::borrow::Cow;
-use std::borrow::Cow::*;
-
-
-#[derive(Copy, Clone, Debug)]
-pub struct Shift {
-    index : usize,
-    delta : isize,
-}
 
-/// The opportunity `i`, index-corrected for use in the original string.
-pub fn realign(i : usize, shifts : &[Shift]) -> usize {
-    (i as isize - shift_at(i, shifts)) as usize
-}
+use hyphenation_commons::Folding;
 
-/// The shift at index `i` in the refolded string.
-fn shift_at(i : usize, shifts : &[Shift]) -> isize {
-    shifts.iter()
-          .rev()
-          .find(|&&shift| i > shift.index)
-          .map_or(0, |&shift| shift.delta)
-}
+use crate::alignment::Span;
 
-fn shifts(word : &str) -> Vec<Shift> {
-    word.match_indices('İ')
-        .map(|(i, _)| (i, -1))
-        .scan(0, |delta, (i, d)| {
-            let index = i as isize + *delta;
-            *delta += d;
-            Some(Shift { index : index as usize,
-                         delta : *delta, })
-        })
-        .collect()
-}
 
+/// Should the original string not be folded already, fold it and provide the
+/// spans it altered. Otherwise, merely borrow it.
+pub fn fold(folding : Folding, original : &str) -> (Cow<'_, str>, Vec<Span>) {
+    if let Cow::Borrowed(_) = folding.apply(original) {
+        return (Cow::Borrowed(original), vec![]);
+    }
 
-/// Should the original string contain special-cased codepoints, refold it
-/// for hyphenation and provide the induced index shifts. Otherwise, merely
-/// ensure that it is lowercase.
-pub fn refold(original : &str) -> (Cow<str>, Vec<Shift>) {
-    if original.chars().any(|c| c.is_uppercase()) {
-        let lowercase = original.to_lowercase();
-        // There is only one code point which changes size when lowercased,
-        // thus comparing string lengths is sufficient to determine whether
-        // it was present in the original word.
-        if original.len() != lowercase.len() {
-            (Owned(refold_lowercase(&lowercase)), shifts(original))
-        } else {
-            (Owned(lowercase), vec![])
+    let mut folded = String::with_capacity(original.len());
+    let mut spans = vec![];
+    for (i, c) in original.char_indices() {
+        let (start, end) = (folded.len(), i + c.len_utf8());
+        folding.fold_char(c, &original[end ..], &mut folded);
+        let piece = &folded[start ..];
+        if piece.len() != c.len_utf8() || piece.chars().nth(1).is_some() {
+            spans.push(Span { start,
+                              end : folded.len(),
+                              original : (i, end) });
         }
-    } else {
-        (Borrowed(original), vec![])
     }
-}
 
-/// Substitute lowercase sequences that would interfere with hyphenation.
-/// Canonical equivalence is not necessarily preserved.
-fn refold_lowercase(lowercase : &str) -> String { lowercase.replace("i\u{307}", "i") }
+    (Cow::Owned(folded), spans)
+}
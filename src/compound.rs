@@ -0,0 +1,298 @@
+/*! Hyphenation of compound words
+
+Compounds are words made of words. Some are joined by hyphens or slashes, as
+English *hard-nosed* or *input/output*; others are closed, as German
+*Silbentrennung* (*Silben* + *Trennung*), and common in German, Dutch and the
+Scandinavian languages. Typesetting conventions favor breaking compounds at
+their joints, and hyphenate each component as a word of its own.
+
+A [`CompoundHyphenator`] splits words at hyphens and slashes, and hyphenates
+each component on its own, so that the minima of the dictionary apply to each
+component rather than to the compound as a whole:
+
+```ignore
+use hyphenation::compound::CompoundHyphenator;
+
+// As a whole, "mother-in-law" would break as "moth-er-in-law".
+assert_eq!(en_us.hyphenate("mother-in-law").breaks, vec![4]);
+
+let compounds = CompoundHyphenator::new(&en_us);
+assert!(compounds.hyphenate("mother-in-law").breaks.is_empty());
+```
+
+Hyphens and slashes are left as they are, and no break is reported at them:
+the word may already break there, and a break would set a second hyphen.
+
+Closed compounds are split by [`Stems`], a set of known stems, which finds
+their joints by dividing each component into as few stems as possible. Joints
+are breaks of [`Quality::Joint`], which outranks the breaks found by
+patterns:
+
+```ignore
+use hyphenation::compound::{CompoundHyphenator, Stems};
+
+let stems = Stems::from_words(&de, vec!["silben", "trennung"]) ?;
+let compounds = CompoundHyphenator::new(&de).with_stems(stems);
+let hyphenated = compounds.hyphenate("Silbentrennung");
+
+// Sil-ben·tren-nung
+let qualities : Vec<_> = hyphenated.breaks.iter().map(|b| b.quality).collect();
+assert_eq!(qualities, vec![Quality::Pattern(1), Quality::Joint, Quality::Pattern(3)]);
+```
+
+Linking elements, such as the *s* of German *Arbeitsamt*, are not stems of
+their own; they should be given with the stem they follow, as *arbeits*.
+
+
+[`CompoundHyphenator`]: struct.CompoundHyphenator.html
+[`Stems`]: struct.Stems.html
+[`Quality::Joint`]: ../score/enum.Quality.html#variant.Joint
+*/
+
+use std::fmt;
+
+use fst::Set;
+use hyphenation_commons::dictionary::extended::Subregion;
+use hyphenation_commons::dictionary::Error;
+use hyphenation_commons::{caseless, HyphenationOptions};
+
+use crate::hyphenator::{prepare, Break, Hyphenator, Word};
+use crate::score::{keep_best, Quality, Weigh, Weighted};
+use crate::text::Offset;
+
+
+/// Opportunities that can be set at any index, without altering the word.
+pub trait Plain {
+    /// A plain break at the given byte index.
+    fn at(index : usize) -> Self;
+}
+
+impl Plain for usize {
+    #[inline]
+    fn at(index : usize) -> Self { index }
+}
+
+impl Plain for (usize, Option<&Subregion>) {
+    #[inline]
+    fn at(index : usize) -> Self { (index, None) }
+}
+
+
+/// A set of stems, by which closed compounds are divided into their
+/// components.
+///
+/// The set is a finite state transducer, stored in bytes of type `D`: owned
+/// by default, but possibly borrowed from a buffer or memory map.
+#[derive(Clone)]
+pub struct Stems<D : AsRef<[u8]> = Vec<u8>>(Set<D>);
+
+impl Stems {
+    /// Build a set from the given stems, normalized and case-folded as the
+    /// dictionary with which they are to be used prepares words.
+    pub fn from_words<'h, H, I, S>(dictionary : &H, words : I) -> Result<Self, Error>
+        where H : Hyphenator<'h>,
+              I : IntoIterator<Item = S>,
+              S : AsRef<str>
+    {
+        let (form, folding) = (dictionary.normalization(), dictionary.folding());
+        let mut words : Vec<String> = words.into_iter()
+                                           .map(|w| caseless(w.as_ref(), form, folding))
+                                           .collect();
+        words.sort();
+        words.dedup();
+        Ok(Stems(Set::from_iter(words)?))
+    }
+}
+
+impl<D : AsRef<[u8]>> Stems<D> {
+    /// The set of stems serialized in the given bytes, as built by the `fst`
+    /// crate.
+    pub fn from_bytes(bs : D) -> Result<Self, Error> { Ok(Stems(Set::new(bs)?)) }
+
+    /// Whether the set holds the given stem.
+    pub fn contains(&self, stem : &str) -> bool { self.0.contains(stem) }
+
+    /// The number of stems in the set.
+    pub fn len(&self) -> usize { self.0.len() }
+
+    /// Whether the set holds no stems.
+    pub fn is_empty(&self) -> bool { self.0.is_empty() }
+
+    /// The byte indices of the joints between the stems of a word, case-folded
+    /// and normalized as the stems are.
+    ///
+    /// The word is divided into as few stems as possible; should it not be
+    /// made entirely of stems, it has no joints.
+    pub fn joints(&self, word : &str) -> Vec<usize> {
+        let bounds : Vec<usize> = word.char_indices()
+                                      .map(|(i, _)| i)
+                                      .skip(1)
+                                      .chain(Some(word.len()))
+                                      .collect();
+
+        // The fewest stems that make up the word up to each index, and the
+        // index at which the last of them begins.
+        let mut fewest : Vec<Option<(usize, usize)>> = vec![None; word.len() + 1];
+        fewest[0] = Some((0, 0));
+        for (n, &start) in Some(&0).into_iter().chain(&bounds).enumerate() {
+            let count = match fewest[start] {
+                Some((count, _)) => count,
+                None => continue,
+            };
+            for &end in &bounds[n ..] {
+                let shorter = fewest[end].map_or(true, |(c, _)| count + 1 < c);
+                if shorter && self.contains(&word[start .. end]) {
+                    fewest[end] = Some((count + 1, start));
+                }
+            }
+        }
+
+        let mut joints = vec![];
+        let mut end = word.len();
+        if fewest[end].is_none() {
+            return joints;
+        }
+        while let Some((_, start)) = fewest[end].filter(|&(_, start)| start > 0) {
+            joints.push(start);
+            end = start;
+        }
+        joints.reverse();
+        joints
+    }
+}
+
+impl<D : AsRef<[u8]>> fmt::Debug for Stems<D> {
+    fn fmt(&self, f : &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Stems").field(&self.0.len()).finish()
+    }
+}
+
+
+/// A hyphenator for compound words, which hyphenates each of their
+/// components on its own, and marks the joints between them.
+#[derive(Clone, Debug)]
+pub struct CompoundHyphenator<'h, H, D : AsRef<[u8]> = Vec<u8>> {
+    hyphenator : &'h H,
+    stems :      Option<Stems<D>>,
+}
+
+impl<'h, H> CompoundHyphenator<'h, H> {
+    /// Prepare the given dictionary for the hyphenation of compounds joined by
+    /// hyphens or slashes.
+    pub fn new(hyphenator : &'h H) -> Self { CompoundHyphenator { hyphenator, stems : None } }
+}
+
+impl<'h, H, D : AsRef<[u8]>> CompoundHyphenator<'h, H, D> {
+    /// Further divide closed compounds with the given stems.
+    pub fn with_stems<E : AsRef<[u8]>>(self, stems : Stems<E>) -> CompoundHyphenator<'h, H, E> {
+        CompoundHyphenator { hyphenator : self.hyphenator,
+                             stems :      Some(stems), }
+    }
+}
+
+impl<'h, H : Hyphenator<'h>, D : AsRef<[u8]>> CompoundHyphenator<'h, H, D> {
+    /// The components of the word, paired with their byte index: the parts
+    /// of the word between hyphens and slashes, further divided at the joints
+    /// found by the stems, if any.
+    pub fn components<'t>(&self, word : &'t str) -> Vec<(usize, &'t str)> {
+        self.split(word)
+            .into_iter()
+            .flat_map(|(i, part, joints)| {
+                pieces(part, &joints).map(move |(j, piece)| (i + j, piece))
+                                     .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+
+    /// The parts of the word between hyphens and slashes, with the joints
+    /// found in each by the stems.
+    fn split<'t>(&self, word : &'t str) -> Vec<(usize, &'t str, Vec<usize>)> {
+        let mut start = 0;
+        word.split(is_delimiter)
+            .map(|part| {
+                let i = start;
+                start += part.len();
+                start += word[start ..].chars().next().map_or(0, char::len_utf8);
+                (i, part)
+            })
+            .filter(|&(_, part)| !part.is_empty())
+            .map(|(i, part)| {
+                let joints = match self.stems {
+                    Some(ref stems) => {
+                        let prepared = prepare(part,
+                                               self.hyphenator.normalization(),
+                                               self.hyphenator.folding());
+                        let mut joints : Vec<usize> = stems.joints(&prepared.word)
+                                                           .into_iter()
+                                                           .filter_map(|j| prepared.realign(j))
+                                                           .collect();
+                        joints.dedup();
+                        joints
+                    }
+                    None => vec![],
+                };
+                (i, part, joints)
+            })
+            .collect()
+    }
+}
+
+impl<'h, H, D> CompoundHyphenator<'h, H, D>
+    where H : Weigh<'h>,
+          H::Opportunity : Break + Offset + Plain,
+          D : AsRef<[u8]>
+{
+    /// Hyphenate each component of the word, as given by `components`, and
+    /// break the word at the joints between closed components. Breaks are
+    /// weighed, as by [`Weigh::hyphenate_weighted`]; joints are of
+    /// `Quality::Joint`.
+    ///
+    /// [`Weigh::hyphenate_weighted`]: ../score/trait.Weigh.html#tymethod.hyphenate_weighted
+    pub fn hyphenate<'t>(&self, word : &'t str) -> Word<'t, Weighted<H::Opportunity>> {
+        self.hyphenate_with(word, &self.hyphenator.options())
+    }
+
+    /// Hyphenate the word as `hyphenate` does, within the limits of the given
+    /// options rather than those of the dictionary. Minima and minimum length
+    /// apply to each component, but the number of breaks to the whole word,
+    /// whose best breaks are kept, joints included.
+    pub fn hyphenate_with<'t>(&self,
+                              word : &'t str,
+                              options : &HyphenationOptions)
+                              -> Word<'t, Weighted<H::Opportunity>> {
+        let unlimited = options.unlimited();
+        let mut breaks = vec![];
+        for (i, part, joints) in self.split(word) {
+            for (j, piece) in pieces(part, &joints) {
+                if j > 0 {
+                    breaks.push(Weighted { opportunity : H::Opportunity::at(i + j),
+                                           quality :     Quality::Joint, });
+                }
+                let hyphenated = self.hyphenator.hyphenate_weighted_with(piece, &unlimited);
+                breaks.extend(hyphenated.breaks.into_iter().map(|b| b.offset(i + j)));
+            }
+        }
+
+        if let Some(max) = options.max_breaks {
+            keep_best(&mut breaks, max, word.len());
+        }
+        Word { text : word, breaks }
+    }
+}
+
+
+/// The pieces of a part of a word between its joints, paired with their byte
+/// index within the part.
+fn pieces<'t, 'j>(part : &'t str,
+                  joints : &'j [usize])
+                  -> impl Iterator<Item = (usize, &'t str)> + 'j
+    where 't : 'j
+{
+    let starts = Some(0).into_iter().chain(joints.iter().copied());
+    let ends = joints.iter().copied().chain(Some(part.len()));
+    starts.zip(ends)
+          .map(move |(start, end)| (start, &part[start .. end]))
+}
+
+#[inline]
+fn is_delimiter(c : char) -> bool { matches!(c, '-' | '\u{2010}' | '\u{2011}' | '/') }
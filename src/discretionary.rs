@@ -0,0 +1,209 @@
+/*! Rendering of hyphenated words for typesetting systems
+
+A break found by hyphenation is a *discretionary*, in the sense of TeX: text
+to be set before the line break, text to be set after it, and text to be set
+should the line not break there at all. Standard breaks set a hyphen before
+the break and nothing otherwise; extended breaks may further alter the
+letters around them, as in Hungarian *asszonnyal*, which breaks as
+*asz-szony-nyal*.
+
+[`Word::discretionaries`] describes each break of a hyphenated word as a
+[`Discretionary`], keeping both its broken and unbroken spelling, whichever
+the dictionary that found it; and [`Word::render`] writes out the word with a
+[`Render`]er for the target markup:
+
+```ignore
+use hyphenation::discretionary::{Latex, Plaintext, Typst};
+
+let hyphenated = en_us.hyphenate("hyphenation");
+assert_eq!(hyphenated.render(&Latex), r"hy\-phen\-a\-tion");
+assert_eq!(hyphenated.render(&Typst), "hy#sym.shy;phen#sym.shy;a#sym.shy;tion");
+assert_eq!(hyphenated.render(&Plaintext::SoftHyphen), "hy\u{ad}phen\u{ad}a\u{ad}tion");
+
+let hyphenated = hu.hyphenate("asszonnyal");
+assert_eq!(hyphenated.render(&Latex),
+           r"a\discretionary{sz-}{}{s}szo\discretionary{ny-}{ny}{nny}al");
+```
+
+Only LaTeX can express alterations around the break; other renderers keep
+the unbroken spelling of the word, and mark the break within it.
+
+
+[`Word::discretionaries`]: ../hyphenator/struct.Word.html#method.discretionaries
+[`Word::render`]: ../hyphenator/struct.Word.html#method.render
+[`Discretionary`]: struct.Discretionary.html
+[`Render`]: trait.Render.html
+*/
+
+use std::ops::Range;
+
+use crate::hyphenator::{Break, Word};
+
+
+/// A break, with the text to be set on either side of it, or in its stead if
+/// the line does not break there.
+///
+/// This is a uniform view of the opportunities of both `Standard` and
+/// `Extended` dictionaries. Standard breaks set nothing but a hyphen, while
+/// extended breaks may replace the text around them: German *backen*, for
+/// instance, breaks as *bak-ken*, with the `c` replaced by `k` before the
+/// break.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Discretionary<'a> {
+    /// The byte index of the break within the word.
+    pub index :      usize,
+    /// The byte range of the word that the break replaces, which contains
+    /// `index`. It is empty for breaks that do not alter the word.
+    pub span :       Range<usize>,
+    /// The text to be set before the break, ahead of the hyphen.
+    pub pre_break :  &'a str,
+    /// The text to be set after the break.
+    pub post_break : &'a str,
+    /// The text to be set if the line does not break; that is, the text of
+    /// the word within `span`.
+    pub no_break :   &'a str,
+}
+
+impl<'a> Discretionary<'a> {
+    /// The discretionary for a break in the given word.
+    pub fn new<B>(word : &'a str, opportunity : &'a B) -> Self
+        where B : Break
+    {
+        let index = opportunity.index();
+        let (span, pre_break, post_break) =
+            opportunity.substitution().unwrap_or((index .. index, "", ""));
+        Discretionary { index,
+                        no_break : &word[span.clone()],
+                        span,
+                        pre_break,
+                        post_break }
+    }
+
+    /// Whether the break leaves the word unaltered, beside the hyphen.
+    pub fn is_plain(&self) -> bool {
+        self.pre_break.is_empty() && self.post_break.is_empty() && self.no_break.is_empty()
+    }
+
+    /// The text of the word within `span`, before and after the break.
+    pub fn split_no_break(&self) -> (&'a str, &'a str) {
+        let at = self.index.clamp(self.span.start, self.span.end) - self.span.start;
+        self.no_break.split_at(at)
+    }
+}
+
+
+impl<'t, B : Break> Word<'t, B> {
+    /// The discretionaries for the breaks of the word, in order.
+    ///
+    /// Should the alterations of consecutive breaks overlap, the latter only
+    /// alters what the former left unaltered.
+    pub fn discretionaries(&self) -> Vec<Discretionary<'_>> {
+        let mut end = 0;
+        self.breaks
+            .iter()
+            .map(|b| {
+                let mut d = Discretionary::new(self.text, b);
+                if d.span.start < end {
+                    d.span.start = end.min(d.span.end);
+                    d.no_break = &self.text[d.span.clone()];
+                }
+                end = d.span.end;
+                d
+            })
+            .collect()
+    }
+
+    /// Write out the word with the given renderer.
+    pub fn render<R>(&self, renderer : &R) -> String
+        where R : Render + ?Sized
+    {
+        let mut output = String::with_capacity(self.text.len() + self.breaks.len() * 4);
+        let mut cursor = 0;
+        for d in self.discretionaries() {
+            renderer.text(&self.text[cursor .. d.span.start], &mut output);
+            renderer.discretionary(&d, &mut output);
+            cursor = d.span.end;
+        }
+        renderer.text(&self.text[cursor ..], &mut output);
+
+        output
+    }
+}
+
+
+/// A representation of hyphenated words in some markup.
+pub trait Render {
+    /// Write out a break.
+    fn discretionary(&self, d : &Discretionary, output : &mut String);
+
+    /// Write out text between breaks. By default, the text is written as is.
+    fn text(&self, text : &str, output : &mut String) { output.push_str(text); }
+}
+
+/// LaTeX, which marks standard breaks with `\-`, and breaks that alter the
+/// word with `\discretionary{pre-}{post}{nobreak}`.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Latex;
+
+impl Render for Latex {
+    fn discretionary(&self, d : &Discretionary, output : &mut String) {
+        if d.is_plain() {
+            output.push_str(r"\-");
+        } else {
+            output.push_str(r"\discretionary{");
+            output.push_str(d.pre_break);
+            output.push_str("-}{");
+            output.push_str(d.post_break);
+            output.push_str("}{");
+            output.push_str(d.no_break);
+            output.push('}');
+        }
+    }
+}
+
+/// Typst, which marks breaks with the soft hyphen symbol `#sym.shy`,
+/// terminated by a semicolon so that it can be followed by letters.
+///
+/// Typst has no discretionaries; breaks that alter the word are marked within
+/// its unbroken spelling.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Typst;
+
+impl Render for Typst {
+    fn discretionary(&self, d : &Discretionary, output : &mut String) {
+        mark_unbroken(d, "#sym.shy;", output);
+    }
+}
+
+/// Plain text, as understood by browsers, word processors and ICU's line
+/// breaking: invisible characters marking where lines may break.
+///
+/// Breaks that alter the word are marked within its unbroken spelling.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Plaintext {
+    /// U+00AD SOFT HYPHEN, which shows a hyphen if the line breaks.
+    #[default]
+    SoftHyphen,
+    /// U+200B ZERO WIDTH SPACE, which allows a break without a hyphen.
+    ZeroWidthSpace,
+}
+
+impl Render for Plaintext {
+    fn discretionary(&self, d : &Discretionary, output : &mut String) {
+        let mark = match *self {
+            Plaintext::SoftHyphen => "\u{ad}",
+            Plaintext::ZeroWidthSpace => "\u{200b}",
+        };
+        mark_unbroken(d, mark, output);
+    }
+}
+
+
+/// Write out the unbroken spelling of a discretionary, with the mark at the
+/// break.
+fn mark_unbroken(d : &Discretionary, mark : &str, output : &mut String) {
+    let (before, after) = d.split_no_break();
+    output.push_str(before);
+    output.push_str(mark);
+    output.push_str(after);
+}
@@ -0,0 +1,418 @@
+/*! Hyphenation of HTML
+
+Pre-hyphenating web content spares browsers without reliable support for CSS
+`hyphens: auto` the work of finding breaks. The [`HtmlHyphenator`] inserts a
+soft hyphen at every break in the text of an HTML document, leaving the markup
+itself untouched:
+
+```ignore
+use hyphenation::html::{HtmlHyphenator, SoftHyphen};
+
+let html = HtmlHyphenator::new(Language::EnglishUS, &en_us)
+    .dictionary(Language::German1996, &de_1996)
+    .soft_hyphen(SoftHyphen::Entity);
+
+let hyphenated = html.hyphenate(r#"<p title="hyphenation">hyphenation
+    <span lang="de-1996">Silbentrennung</span> <code>hyphenation</code></p>"#) ?;
+assert_eq!(hyphenated, r#"<p title="hyphenation">hy&shy;phen&shy;a&shy;tion
+    <span lang="de-1996">Sil&shy;ben&shy;tren&shy;nung</span> <code>hyphenation</code></p>"#);
+```
+
+Only text is hyphenated: tags, attribute values, comments and character
+references are copied as they are, and so is the content of elements that
+either hold code or no markup at all – `code`, `pre`, `kbd`, `samp`,
+`script`, `style`, `textarea` and `title`.
+
+The language of the text is determined by the `lang` attribute of the
+nearest element that has one, or by the default language of the
+hyphenator otherwise. Text in a language for which no dictionary was given
+is left unhyphenated. Language tags are resolved to a [`Language`] as by
+[`registry::resolve`], regardless of case, choosing the most common variety
+of a language where the tag names none, and dropping the trailing subtags of
+malformed tags; thus, `de-1996-DE` and `de-AT` are hyphenated as `de-1996`.
+
+
+## Malformed markup
+
+Like browsers, the hyphenator tolerates unclosed elements and stray end
+tags. Markup that cannot be delimited at all – a tag, comment or `script`
+that is never closed – makes [`hyphenate`] return an [`Error`] locating
+the offending construct, rather than guess where the text resumes.
+
+
+[`HtmlHyphenator`]: struct.HtmlHyphenator.html
+[`hyphenate`]: struct.HtmlHyphenator.html#method.hyphenate
+[`Error`]: enum.Error.html
+[`Language`]: ../enum.Language.html
+[`registry::resolve`]: ../registry/fn.resolve.html
+*/
+
+use std::error;
+use std::fmt;
+
+use hyphenation_commons::Language;
+
+use crate::hyphenator::Hyphenator;
+use crate::registry::resolve;
+use crate::text::TextHyphenator;
+
+
+/// Elements whose content is not hyphenated.
+const SKIPPED : &[&str] = &["code", "kbd", "pre", "samp", "script", "style", "textarea", "title"];
+
+/// Elements whose content is not markup, and runs until their end tag.
+const RAW_TEXT : &[&str] = &["script", "style", "textarea", "title"];
+
+/// Elements that have no content, nor an end tag.
+const VOID : &[&str] = &["area", "base", "br", "col", "embed", "hr", "img", "input", "link",
+                         "meta", "param", "source", "track", "wbr"];
+
+
+/// The representation of soft hyphens in the output.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum SoftHyphen {
+    /// The soft hyphen character itself, U+00AD.
+    #[default]
+    Character,
+    /// The `&shy;` character reference.
+    Entity,
+}
+
+impl SoftHyphen {
+    fn as_str(self) -> &'static str {
+        match self {
+            SoftHyphen::Character => "\u{ad}",
+            SoftHyphen::Entity => "&shy;",
+        }
+    }
+}
+
+
+/// A hyphenator for HTML documents, which inserts soft hyphens in their text
+/// with the dictionary for its language.
+#[derive(Clone, Debug)]
+pub struct HtmlHyphenator<'h, H> {
+    dictionaries : Vec<(Language, &'h H)>,
+    default :      Option<Language>,
+    soft_hyphen :  SoftHyphen,
+}
+
+impl<'h, H> HtmlHyphenator<'h, H> where H : Hyphenator<'h, Opportunity = usize>
+{
+    /// Prepare to hyphenate documents in the given language, with the given
+    /// dictionary. Elements may declare another language with their `lang`
+    /// attribute.
+    pub fn new(language : Language, hyphenator : &'h H) -> Self {
+        HtmlHyphenator { dictionaries : vec![(language, hyphenator)],
+                         default :      Some(language),
+                         soft_hyphen :  SoftHyphen::default(), }
+    }
+
+    /// Hyphenate text declared to be in the given language with the given
+    /// dictionary.
+    pub fn dictionary(mut self, language : Language, hyphenator : &'h H) -> Self {
+        self.dictionaries.retain(|&(l, _)| l != language);
+        self.dictionaries.push((language, hyphenator));
+        self
+    }
+
+    /// Set the language of text outside any element with a `lang` attribute,
+    /// or `None` to leave such text unhyphenated.
+    pub fn default_language(mut self, language : Option<Language>) -> Self {
+        self.default = language;
+        self
+    }
+
+    /// Set the representation of the soft hyphens to insert.
+    pub fn soft_hyphen(mut self, soft_hyphen : SoftHyphen) -> Self {
+        self.soft_hyphen = soft_hyphen;
+        self
+    }
+
+    /// Insert soft hyphens at every break in the text of the document.
+    pub fn hyphenate(&self, html : &str) -> Result<String, Error> {
+        let mut output = String::with_capacity(html.len() + html.len() / 8);
+        let mut stack = vec![Element { name :     String::new(),
+                                       language : self.default,
+                                       skip :     false, }];
+        let mut i = 0;
+
+        while i < html.len() {
+            let rest = &html[i ..];
+            let current = stack.last().unwrap();
+            let end = match Markup::at(rest) {
+                None => {
+                    // A text run ends at the next `<` that may open markup.
+                    let first = rest.chars().next().map_or(0, char::len_utf8);
+                    let end = rest[first ..].find('<')
+                                            .map_or(html.len(), |j| i + first + j);
+                    let hyphenator = match current.skip {
+                        true => None,
+                        false => current.language.and_then(|l| self.hyphenator(l)),
+                    };
+                    match hyphenator {
+                        Some(h) => self.hyphenate_text(h, &html[i .. end], &mut output),
+                        None => output.push_str(&html[i .. end]),
+                    }
+                    i = end;
+                    continue;
+                }
+                Some(Markup::Comment) => i + delimit(rest, "-->", 4).ok_or(Error::Comment(i))?,
+                Some(Markup::CData) => i + delimit(rest, "]]>", 9).ok_or(Error::Comment(i))?,
+                Some(Markup::Declaration) => i + delimit(rest, ">", 2).ok_or(Error::Tag(i))?,
+                Some(Markup::EndTag) => {
+                    let tag = Tag::parse(&rest[2 ..]).ok_or(Error::Tag(i))?;
+                    // Stray end tags are ignored, and unclosed elements are
+                    // closed along with their parent.
+                    if let Some(k) = stack.iter().rposition(|e| e.name == tag.name) {
+                        stack.truncate(k);
+                    }
+                    i + 2 + tag.length
+                }
+                Some(Markup::StartTag) => {
+                    let tag = Tag::parse(&rest[1 ..]).ok_or(Error::Tag(i))?;
+                    let name = tag.name.as_str();
+                    let mut end = i + 1 + tag.length;
+                    if RAW_TEXT.contains(&name) && !tag.self_closing {
+                        // The content of raw text elements is copied along with
+                        // their end tag, without looking for markup.
+                        match find_end_tag(&html[end ..], name) {
+                            Some(length) => end += length,
+                            None => return Err(Error::Element(tag.name, i)),
+                        }
+                    } else if !(tag.self_closing || VOID.contains(&name)) {
+                        let language = match tag.language {
+                            Some(code) => resolve(code).ok(),
+                            None => current.language,
+                        };
+                        let skip = current.skip || SKIPPED.contains(&name);
+                        stack.push(Element { name : tag.name, language, skip });
+                    }
+                    end
+                }
+            };
+
+            output.push_str(&html[i .. end]);
+            i = end;
+        }
+
+        Ok(output)
+    }
+
+    fn hyphenator(&self, language : Language) -> Option<&'h H> {
+        self.dictionaries
+            .iter()
+            .find(|&&(l, _)| l == language)
+            .map(|&(_, h)| h)
+    }
+
+    /// Hyphenate a run of text, copying its character references unaltered.
+    fn hyphenate_text(&self, hyphenator : &'h H, text : &str, output : &mut String) {
+        let text_hyphenator = TextHyphenator::new(hyphenator);
+        let mut start = 0;
+        for (reference, end) in references(text) {
+            let run = &text[start .. reference];
+            let mut last = 0;
+            for b in text_hyphenator.hyphenate(run).breaks {
+                // Breaks that are already marked need not be marked again.
+                if run[b ..].starts_with('\u{ad}') {
+                    continue;
+                }
+                output.push_str(&run[last .. b]);
+                output.push_str(self.soft_hyphen.as_str());
+                last = b;
+            }
+            output.push_str(&run[last ..]);
+            output.push_str(&text[reference .. end]);
+            start = end;
+        }
+    }
+}
+
+
+/// An open element, with the language and hyphenation of its content.
+#[derive(Clone, Debug)]
+struct Element {
+    name :     String,
+    language : Option<Language>,
+    skip :     bool,
+}
+
+/// The kinds of markup that can begin at a `<`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Markup {
+    StartTag,
+    EndTag,
+    Comment,
+    CData,
+    Declaration,
+}
+
+impl Markup {
+    /// The markup that the text begins with, if any. A `<` that does not
+    /// begin a tag is text, as in HTML.
+    fn at(s : &str) -> Option<Markup> {
+        let mut bytes = s.bytes().skip(1);
+        match (s.as_bytes().first(), bytes.next(), bytes.next()) {
+            (Some(b'<'), Some(b), _) if b.is_ascii_alphabetic() => Some(Markup::StartTag),
+            (Some(b'<'), Some(b'/'), Some(b)) if b.is_ascii_alphabetic() => Some(Markup::EndTag),
+            (Some(b'<'), Some(b'!'), _) if s.starts_with("<!--") => Some(Markup::Comment),
+            (Some(b'<'), Some(b'!'), _) if s.starts_with("<![CDATA[") => Some(Markup::CData),
+            (Some(b'<'), Some(b'!'), _) | (Some(b'<'), Some(b'?'), _) => Some(Markup::Declaration),
+            _ => None,
+        }
+    }
+}
+
+/// The parts of a tag relevant to hyphenation.
+#[derive(Clone, Debug)]
+struct Tag<'a> {
+    /// The name of the tag, in lowercase.
+    name :         String,
+    /// The value of its `lang` attribute, if any.
+    language :     Option<&'a str>,
+    self_closing : bool,
+    /// The length of the tag in bytes, from its name to its closing `>`.
+    length :       usize,
+}
+
+impl<'a> Tag<'a> {
+    /// Parse the tag whose name begins the text, if it is closed.
+    fn parse(s : &'a str) -> Option<Self> {
+        let is_name = |c : char| !(c.is_whitespace() || c == '/' || c == '>' || c == '=');
+        let name_end = s.find(|c| !is_name(c)).unwrap_or(s.len());
+        let name = s[.. name_end].to_ascii_lowercase();
+        let mut language = None;
+        let mut self_closing = false;
+
+        let mut i = name_end;
+        loop {
+            let rest = &s[i ..];
+            let c = rest.chars().next()?;
+            if c == '>' {
+                return Some(Tag { name,
+                                  language,
+                                  self_closing,
+                                  length : i + 1 });
+            }
+            if c.is_whitespace() || c == '/' {
+                self_closing = c == '/';
+                i += c.len_utf8();
+                continue;
+            }
+
+            self_closing = false;
+            let attribute_end = rest.find(|c| !is_name(c)).unwrap_or(rest.len()).max(1);
+            let attribute = &rest[.. attribute_end];
+            i += attribute_end;
+
+            let after = s[i ..].trim_start();
+            if !after.starts_with('=') {
+                continue;
+            }
+            i = s.len() - after.len() + 1;
+            let after = s[i ..].trim_start();
+            i = s.len() - after.len();
+            let value = match after.chars().next()? {
+                q @ '"' | q @ '\'' => {
+                    let length = after[1 ..].find(q)?;
+                    i += length + 2;
+                    &after[1 .. 1 + length]
+                }
+                _ => {
+                    let length = after.find(|c : char| c.is_whitespace() || c == '>')
+                                      .unwrap_or(after.len());
+                    i += length;
+                    &after[.. length]
+                }
+            };
+            if attribute.eq_ignore_ascii_case("lang") || attribute.eq_ignore_ascii_case("xml:lang")
+            {
+                language = Some(value);
+            }
+        }
+    }
+}
+
+
+/// The length of the construct that opens the text, up to and including
+/// the delimiter that closes it, searched for from `start`.
+fn delimit(s : &str, delimiter : &str, start : usize) -> Option<usize> {
+    s.get(start ..)?
+     .find(delimiter)
+     .map(|i| start + i + delimiter.len())
+}
+
+/// The length of the text up to and including the end tag of the given
+/// element.
+fn find_end_tag(s : &str, name : &str) -> Option<usize> {
+    let mut i = 0;
+    while let Some(j) = s[i ..].find("</") {
+        let start = i + j;
+        let after = &s[start + 2 ..];
+        let matches = after.get(.. name.len())
+                           .is_some_and(|n| n.eq_ignore_ascii_case(name))
+                      && after[name.len() ..].starts_with(|c : char| {
+                                                 c.is_whitespace() || c == '/' || c == '>'
+                                             });
+        if matches {
+            return delimit(&s[start ..], ">", 2).map(|length| start + length);
+        }
+        i = start + 2;
+    }
+
+    None
+}
+
+/// The spans of the character references in the text, such as `&amp;`,
+/// followed by an empty span at its end.
+fn references(text : &str) -> impl Iterator<Item = (usize, usize)> + '_ {
+    let mut i = 0;
+    std::iter::from_fn(move || {
+        if i > text.len() {
+            return None;
+        }
+        while let Some(j) = text[i ..].find('&') {
+            let start = i + j;
+            let name = &text[start + 1 ..];
+            let length = name.find(|c : char| !(c.is_ascii_alphanumeric() || c == '#'))
+                             .unwrap_or(name.len());
+            if length > 0 && name[length ..].starts_with(';') {
+                i = start + length + 2;
+                return Some((start, i));
+            }
+            i = start + 1;
+        }
+        let end = text.len();
+        i = end + 1;
+        Some((end, end))
+    })
+}
+
+
+/// Failure modes of HTML hyphenation, for markup whose end cannot be found.
+/// Each error holds the byte index of the offending markup.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// A tag or declaration is not closed by a `>`.
+    Tag(usize),
+    /// A comment or CDATA section is not closed.
+    Comment(usize),
+    /// An element whose content is not markup, such as `script`, has no end
+    /// tag.
+    Element(String, usize),
+}
+
+impl error::Error for Error {}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f : &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::Tag(i) => write!(f, "unclosed tag at byte {}", i),
+            Error::Comment(i) => write!(f, "unclosed comment at byte {}", i),
+            Error::Element(ref name, i) => {
+                write!(f, "`{}` element at byte {} has no end tag", name, i)
+            }
+        }
+    }
+}
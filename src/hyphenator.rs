@@ -3,11 +3,18 @@ Methods for hyphenation dictionaries
 */
 
 use std::borrow::Cow;
+use std::ops::Range;
+
+use unicode_segmentation::{GraphemeCursor, UnicodeSegmentation};
 
 use hyphenation_commons::dictionary::extended::*;
 use hyphenation_commons::dictionary::*;
-use crate::case_folding::{realign, refold, Shift};
-use crate::score::Score;
+use hyphenation_commons::{Folding, HyphenationOptions, Normalization};
+use crate::alignment::{self, Span};
+use crate::case_folding::fold;
+use crate::normalization::normalize;
+use crate::score::{patterns, weigh};
+use crate::text::{Offset, TextHyphenator};
 
 
 /// The indices of soft hyphens (U+00AD) within the string, if any. Existing
@@ -34,6 +41,38 @@ pub struct Word<'t, Break> {
 }
 
 
+/// A word break produced by a hyphenator.
+///
+/// For a uniform view of the text to be set around breaks, see
+/// [`Discretionary`](../discretionary/struct.Discretionary.html).
+pub trait Break {
+    /// The byte index of the break within the word.
+    fn index(&self) -> usize;
+
+    /// Should the break alter the word around it, the byte range of the
+    /// altered text, along with the text to be set before and after the
+    /// break in its stead.
+    fn substitution(&self) -> Option<(Range<usize>, &str, &str)> { None }
+}
+
+impl Break for usize {
+    #[inline]
+    fn index(&self) -> usize { *self }
+}
+
+impl Break for (usize, Option<&Subregion>) {
+    #[inline]
+    fn index(&self) -> usize { self.0 }
+
+    fn substitution(&self) -> Option<(Range<usize>, &str, &str)> {
+        self.1.map(|subr| {
+                  let (pre, post) = subr.substitution.split_at(subr.breakpoint);
+                  (self.0 - subr.left .. self.0 + subr.right, pre, post)
+              })
+    }
+}
+
+
 /// A dictionary capable of hyphenating individual words.
 ///
 /// For the purpose of hyphenation, a "word" should not be a compound in
@@ -56,15 +95,49 @@ pub trait Hyphenator<'h> {
     /// Soft hyphens take priority over dictionary hyphenation; if the word
     /// contains any, they will be returned as the only breaks available.
     ///
-    /// This method is case-insensitive.
-    fn hyphenate<'t>(&'h self, word : &'t str) -> Word<'t, Self::Opportunity>;
+    /// This method is case-insensitive: the word is normalized to the form of
+    /// the dictionary's patterns, and case-folded as they are. Breaks are byte
+    /// indices into the word as given, and always fall between its extended
+    /// grapheme clusters. They are limited by the dictionary's `options`.
+    fn hyphenate<'t>(&'h self, word : &'t str) -> Word<'t, Self::Opportunity> {
+        self.hyphenate_with(word, &self.options())
+    }
+
+    /// Hyphenate a word as `hyphenate` does, within the limits of the given
+    /// options rather than those of the dictionary.
+    fn hyphenate_with<'t>(&'h self,
+                          word : &'t str,
+                          options : &HyphenationOptions)
+                          -> Word<'t, Self::Opportunity>;
+
+    /// Hyphenate running text, segmenting it into words and hyphenating
+    /// each of them. Breaks are byte indices into the whole text.
+    ///
+    /// Whitespace, punctuation and numbers are left unbroken. For details,
+    /// refer to the [`text`](../text/index.html) module.
+    fn hyphenate_text<'t>(&'h self, text : &'t str) -> Word<'t, Self::Opportunity>
+        where Self : Sized,
+              Self::Opportunity : Offset
+    {
+        TextHyphenator::new(self).hyphenate(text)
+    }
 
     /// The hyphenation opportunities that our dictionary can find in the given
-    /// word. The word should be lowercase.
+    /// word. The word should be normalized to the form given by
+    /// `normalization`, and case-folded as by `folding`.
     fn opportunities(&'h self, lowercase_word : &str) -> Vec<Self::Opportunity> {
-        match self.boundaries(lowercase_word) {
+        self.opportunities_with(lowercase_word, &self.options())
+    }
+
+    /// The hyphenation opportunities that our dictionary can find in the given
+    /// word, within the minima and minimum length of the given options.
+    fn opportunities_with(&'h self,
+                          lowercase_word : &str,
+                          options : &HyphenationOptions)
+                          -> Vec<Self::Opportunity> {
+        match self.boundaries_with(lowercase_word, options) {
             None => vec![],
-            Some(mins) => match self.exception(lowercase_word) {
+            Some(mins) => match self.exception_within(lowercase_word, mins) {
                 None => self.opportunities_within(lowercase_word, mins),
                 Some(known) => known,
             },
@@ -108,77 +181,122 @@ pub trait Hyphenator<'h> {
     /// specified opportunities; otherwise, return none.
     fn remove_exception(&mut self, word : &str) -> Option<Vec<Self::Exact>>;
 
-    /// The number of `char`s from the start and end of a word where breaks may
-    /// not occur, according to dictionary parameters.
-    fn unbreakable_chars(&self) -> (usize, usize);
+    /// The limits within which the dictionary hyphenates words.
+    fn options(&self) -> HyphenationOptions;
+
+    /// The number of graphemes from the start and end of a word where breaks
+    /// may not occur, according to dictionary parameters.
+    fn unbreakable_chars(&self) -> (usize, usize) { self.options().minima }
+
+    /// The normalization form of the dictionary's patterns, to which words are
+    /// normalized before hyphenation.
+    fn normalization(&self) -> Normalization { Normalization::None }
+
+    /// The case folding of the dictionary's patterns, by which words are
+    /// folded before hyphenation.
+    fn folding(&self) -> Folding { Folding::Default }
 
     /// The byte indices delimiting the substring where breaks may occur, unless
-    /// the word is too short to be hyphenated.
+    /// the word is too short to be hyphenated. Minima are counted in extended
+    /// grapheme clusters, such that a letter and its combining marks count as
+    /// one.
     fn boundaries(&self, word : &str) -> Option<(usize, usize)> {
-        let (l_min, r_min) = self.unbreakable_chars();
-        let length_min = l_min + r_min;
-        if word.chars().count() >= length_min {
-            (word.char_indices().nth(l_min).unwrap().0,
-             word.char_indices()
-                 .rev()
-                 .nth(r_min.saturating_sub(1))
-                 .unwrap()
-                 .0)
-                    .into()
-        } else {
-            None
+        self.boundaries_with(word, &self.options())
+    }
+
+    /// The byte indices delimiting the substring where breaks may occur
+    /// within the limits of the given options, unless the word is too short
+    /// to be hyphenated.
+    fn boundaries_with(&self,
+                       word : &str,
+                       options : &HyphenationOptions)
+                       -> Option<(usize, usize)> {
+        let (l_min, r_min) = options.minima;
+        let starts : Vec<usize> = word.grapheme_indices(true).map(|(i, _)| i).collect();
+        if starts.is_empty() || starts.len() < (l_min + r_min).max(options.min_length) {
+            return None;
         }
+        let l = starts.get(l_min).copied().unwrap_or(word.len());
+        let r = starts[starts.len() - r_min.max(1)];
+        Some((l, r))
     }
 }
 
 
+/// Whether the byte index falls between two extended grapheme clusters of the
+/// word, such that a break there would not sever a letter from its combining
+/// marks, nor split a syllable cluster of Indic or Southeast Asian scripts.
+pub fn is_grapheme_boundary(word : &str, i : usize) -> bool {
+    word.is_char_boundary(i)
+    && GraphemeCursor::new(i, word.len(), true).is_boundary(word, 0)
+                                               .unwrap_or(false)
+}
+
+
+/// A word prepared for hyphenation: normalized, case-folded, and normalized
+/// again, as patterns are.
 #[derive(Debug, Clone)]
-struct Prepared<'t> {
-    word :   Cow<'t, str>,
-    shifts : Vec<Shift>,
+pub(crate) struct Prepared<'t> {
+    pub word : Cow<'t, str>,
+    original : &'t str,
+    steps :    Vec<Vec<Span>>,
 }
 
-fn prepare(text : &str) -> Prepared<'_> {
-    let (word, shifts) = refold(text);
-    Prepared { word, shifts }
+impl<'t> Prepared<'t> {
+    /// The opportunity `i` of the prepared word, index-corrected for use in
+    /// the original word, unless it would fall at the start of the original,
+    /// or within one of its graphemes.
+    pub fn realign(&self, i : usize) -> Option<usize> {
+        let i = self.steps
+                    .iter()
+                    .rev()
+                    .fold(i, |i, spans| alignment::realign(i, spans));
+        Some(i).filter(|&i| i > 0 && is_grapheme_boundary(self.original, i))
+    }
+}
+
+pub(crate) fn prepare(text : &str, form : Normalization, folding : Folding) -> Prepared<'_> {
+    let (normalized, normalization) = normalize(form, text);
+    let (folded, case_folding) = then(normalized, |s| fold(folding, s));
+    let (word, renormalization) = then(folded, |s| normalize(form, s));
+    Prepared { word,
+               original : text,
+               steps : vec![normalization, case_folding, renormalization] }
+}
+
+/// Apply a step of preparation to a word, which may be owned already.
+fn then<'t, F>(word : Cow<'t, str>, step : F) -> (Cow<'t, str>, Vec<Span>)
+    where F : Fn(&str) -> (Cow<'_, str>, Vec<Span>)
+{
+    match word {
+        Cow::Borrowed(word) => step(word),
+        Cow::Owned(word) => {
+            let (stepped, spans) = step(&word);
+            let stepped = match stepped {
+                Cow::Borrowed(_) => None,
+                Cow::Owned(stepped) => Some(stepped),
+            };
+            (Cow::Owned(stepped.unwrap_or(word)), spans)
+        }
+    }
 }
 
 
-impl<'h> Hyphenator<'h> for Standard {
+impl<'h, D : AsRef<[u8]>> Hyphenator<'h> for Standard<D> {
     type Opportunity = usize;
     type Exact = usize;
 
-    fn hyphenate<'t>(&'h self, word : &'t str) -> Word<'t, Self::Opportunity> {
-        let breaks = match soft_hyphen_indices(word) {
-            Some(ops) => ops,
-            None => {
-                let Prepared { ref word,
-                               ref shifts, } = prepare(word);
-                if !shifts.is_empty() {
-                    self.opportunities(word)
-                        .into_iter()
-                        .map(move |o| realign(o, shifts))
-                        .collect()
-                } else {
-                    self.opportunities(word)
-                }
-            }
-        };
-
-        Word { breaks,
-               text : word }
+    fn hyphenate_with<'t>(&'h self,
+                          word : &'t str,
+                          options : &HyphenationOptions)
+                          -> Word<'t, Self::Opportunity> {
+        weigh(self, word, options).unweighted()
     }
 
-    fn opportunities_within(&'h self, word : &str, (l, r) : (usize, usize)) -> Vec<usize> {
-        (1 .. word.len()).zip(self.score(word))
-                         .filter(|&(i, v)| {
-                             let valid = Self::denotes_opportunity(v);
-                             let within_bounds = i >= l && i <= r;
-                             let legal_index = word.is_char_boundary(i);
-                             valid && within_bounds && legal_index
-                         })
-                         .map(|(i, _)| i)
-                         .collect()
+    fn opportunities_within(&'h self, word : &str, bounds : (usize, usize)) -> Vec<usize> {
+        patterns(self, word, bounds).into_iter()
+                                    .map(|w| w.opportunity)
+                                    .collect()
     }
 
     #[inline]
@@ -201,48 +319,34 @@ impl<'h> Hyphenator<'h> for Standard {
     fn remove_exception(&mut self, w : &str) -> Option<Vec<usize>> { self.exceptions.0.remove(w) }
 
     #[inline]
-    fn unbreakable_chars(&self) -> (usize, usize) { self.minima }
+    fn options(&self) -> HyphenationOptions { self.options }
+
+    #[inline]
+    fn normalization(&self) -> Normalization { self.normalization() }
+
+    #[inline]
+    fn folding(&self) -> Folding { self.language().folding() }
 }
 
 
-impl<'h> Hyphenator<'h> for Extended {
+impl<'h, D : AsRef<[u8]>> Hyphenator<'h> for Extended<D> {
     type Opportunity = (usize, Option<&'h Subregion>);
     type Exact = (usize, Option<Subregion>);
 
-    fn hyphenate<'t>(&'h self, word : &'t str) -> Word<'t, Self::Opportunity> {
-        let breaks = match soft_hyphen_indices(word) {
-            Some(ops) => ops.into_iter().map(|i| (i, None)).collect(),
-            None => {
-                let Prepared { ref word,
-                               ref shifts, } = prepare(word);
-                if !shifts.is_empty() {
-                    self.opportunities(word)
-                        .into_iter()
-                        .map(move |(i, subr)| (realign(i, shifts), subr))
-                        .collect()
-                } else {
-                    self.opportunities(word)
-                }
-            }
-        };
-
-        Word { breaks,
-               text : word }
+    fn hyphenate_with<'t>(&'h self,
+                          word : &'t str,
+                          options : &HyphenationOptions)
+                          -> Word<'t, Self::Opportunity> {
+        weigh(self, word, options).unweighted()
     }
 
     fn opportunities_within(&'h self,
                             word : &str,
-                            (l, r) : (usize, usize))
+                            bounds : (usize, usize))
                             -> Vec<Self::Opportunity> {
-        (1 .. word.len()).zip(self.score(word))
-                         .filter(|&(i, v)| {
-                             let valid = Self::denotes_opportunity(v);
-                             let within_bounds = i >= l && i <= r;
-                             let legal_index = word.is_char_boundary(i);
-                             valid && within_bounds && legal_index
-                         })
-                         .map(|(i, (_, subr))| (i, subr))
-                         .collect()
+        patterns(self, word, bounds).into_iter()
+                                    .map(|w| w.opportunity)
+                                    .collect()
     }
 
     #[inline]
@@ -267,5 +371,11 @@ impl<'h> Hyphenator<'h> for Extended {
     }
 
     #[inline]
-    fn unbreakable_chars(&self) -> (usize, usize) { self.minima }
+    fn options(&self) -> HyphenationOptions { self.options }
+
+    #[inline]
+    fn normalization(&self) -> Normalization { self.normalization() }
+
+    #[inline]
+    fn folding(&self) -> Folding { self.language().folding() }
 }
@@ -31,6 +31,9 @@ impl<'m, I, S> Hyphenating<'m, I>
     pub fn segments(self) -> I { self.inner }
 
     /// Set the mark that will be inserted before word breaks.
+    ///
+    /// To render breaks in the markup of a typesetting system, see the
+    /// [`discretionary`](../discretionary/index.html) module.
     pub fn mark_with(&mut self, mark : &'m str) { self.mark = mark; }
 
     /// Build a hyphenating iterator from an iterator over string segments.
@@ -71,6 +74,17 @@ pub trait Iter<'t> {
     type Iter;
 
     fn iter(&'t self) -> Hyphenating<'t, Self::Iter>;
+
+    /// Iterate over the segments of the word, marking them with the given
+    /// mark rather than a hyphen.
+    fn segments_with_mark<'m, S>(&'t self, mark : &'m str) -> Hyphenating<'m, Self::Iter>
+        where Self::Iter : Iterator<Item = S>,
+              S : AsRef<str>
+    {
+        let mut iter = Hyphenating::new(self.iter().segments());
+        iter.mark_with(mark);
+        iter
+    }
 }
 
 impl<'t> Iter<'t> for Word<'t, usize> {
@@ -81,6 +95,14 @@ impl<'t> Iter<'t> for Word<'t, usize> {
     }
 }
 
+impl<'t> Iter<'t> for Word<'t, (usize, Option<&'t Subregion>)> {
+    type Iter = SegmentsExt<'t, Cloned<slice::Iter<'t, (usize, Option<&'t Subregion>)>>>;
+
+    fn iter(&'t self) -> Hyphenating<'t, Self::Iter> {
+        Hyphenating::new(SegmentsExt::new(self.text, self.breaks.iter().cloned()))
+    }
+}
+
 impl<'t> IntoIterator for Word<'t, usize> {
     type Item = String;
     type IntoIter = Hyphenating<'t, Segments<'t, vec::IntoIter<usize>>>;
@@ -171,7 +193,7 @@ impl<'t, I> SegmentsExt<'t, I> {
     fn substitute(&mut self, text : &'t str) -> Cow<'t, str> {
         match self.queued.take() {
             None => Cow::Borrowed(text),
-            Some((skip, ref subst)) => Cow::Owned([subst, &text[skip ..]].concat()),
+            Some((skip, subst)) => Cow::Owned([subst, &text[skip ..]].concat()),
         }
     }
 }
@@ -193,11 +215,15 @@ impl<'t, I> Iterator for SegmentsExt<'t, I>
                 self.start = Some(index);
                 Some(self.substitute(&self.text[start .. index]))
             }
-            Some((index, Some(ref subr))) => {
+            Some((index, Some(subr))) => {
                 let (start, end) = (self.start.unwrap(), index);
                 self.start = Some(index);
 
-                let (segment_start, fore) = self.queued.take().unwrap_or((start, ""));
+                let (segment_start, fore) = self.queued
+                                                .take()
+                                                .map_or((start, ""), |(skip, subst)| {
+                                                    (start + skip, subst)
+                                                });
                 let (segment_end, aft) = {
                     let (subst, queued) = subr.substitution.split_at(subr.breakpoint);
                     if !queued.is_empty() {
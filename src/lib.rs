@@ -26,6 +26,9 @@ let en_us = Standard::from_path(Language::EnglishUS, path_to_dict) ?;
 
 Our English dictionary can now be used as a [`Hyphenator`].
 
+Dictionaries for other pattern sets can also be compiled at runtime with a
+[`DictionaryBuilder`].
+
 
 ### Hyphenators
 
@@ -49,7 +52,13 @@ assert_eq!(hyphenated_segments, &["an-", "frac-", "tu-", "ous"]);
 ```
 
 Both the [`Standard`] and [`Extended`] hyphenators are case-insensitive and
-prioritize existing soft hyphens (U+00AD) over dictionary hyphenation.
+prioritize existing soft hyphens (U+00AD) over dictionary hyphenation. Words
+are normalized to the Unicode normalization form recorded in the dictionary,
+so that "cafe\u{301}" and "caf\u{e9}" are hyphenated alike, and case-folded
+as suits its language, so that "STRAẞE" is hyphenated as "straße"; breaks are
+always given in the word as it was submitted, and never within one of its
+extended grapheme clusters, where they would sever a letter from its combining
+marks or vowel signs. Minima are likewise counted in graphemes.
 
 ```ignore
 let word = "ribonuclease";
@@ -63,6 +72,17 @@ assert_eq!(by_shy, vec!["ri", "\u{00ad}bo", "\u{00ad}nu", "\u{00ad}cle", "\u{00a
 assert_ne!(by_dictionary, by_shy);
 ```
 
+Where words may be broken is further limited by the [`HyphenationOptions`]
+of the dictionary: the minima at either end of a word, the length below
+which words are left whole, and the number of breaks allowed in a word.
+Options may be set on the dictionary, or given for a single word with
+[`hyphenate_with`].
+
+```ignore
+let headline = en_us.options.min_length(8).max_breaks(1);
+assert_eq!(en_us.hyphenate_with("incomprehensibilities", &headline).breaks, &[11]);
+```
+
 
 ## Identifying "words"
 
@@ -76,14 +96,37 @@ level of segmentation, as it affords greater control over the final result
 (such as where to break hyphen-joined compounds, or whether to set a leading
 hyphen on new lines).
 
+For the common case, [`hyphenate_text`] segments running text by Unicode word
+boundaries and hyphenates each word in turn, skipping whitespace, punctuation
+and numbers.
+
+```ignore
+let hyphenated = en_us.hyphenate_text("lucid, inescapable rhythms");
+let marked : String = hyphenated.iter().collect();
+assert_eq!(marked, "lu-cid, in-escapable rhythms");
+```
+
+Compound words can be hyphenated with the [`compound`] module, which splits
+them at hyphens and slashes – and, given a set of known stems, at the joints
+of closed compounds – and hyphenates each component on its own.
+
+HTML documents can be hyphenated with the [`html`] module, which inserts soft
+hyphens in their text while leaving markup and code untouched.
+
 
 [`Hyphenator`]: hyphenator/trait.Hyphenator.html
 [`Standard`]: struct.Standard.html
 [`Language`]: enum.Language.html
 [`Load`]: load/trait.Load.html
+[`DictionaryBuilder`]: builder/struct.DictionaryBuilder.html
 [`hyphenate`]: hyphenator/trait.Hyphenator#tymethod.hyphenate.html
 [iterate]: iter/struct.Hyphenating.html
 [`Extended`]: extended/struct.Extended.html
+[`hyphenate_text`]: hyphenator/trait.Hyphenator.html#method.hyphenate_text
+[`HyphenationOptions`]: struct.HyphenationOptions.html
+[`hyphenate_with`]: hyphenator/trait.Hyphenator.html#tymethod.hyphenate_with
+[`html`]: html/index.html
+[`compound`]: compound/index.html
 */
 
 #![forbid(unsafe_code)]
@@ -91,19 +134,35 @@ hyphen on new lines).
 extern crate bincode;
 extern crate fst;
 extern crate hyphenation_commons;
+extern crate unicode_segmentation;
 
 
+mod alignment;
+pub mod builder;
 mod case_folding;
+pub mod compound;
+pub mod discretionary;
 pub mod extended;
+pub mod html;
 pub mod hyphenator;
 pub mod iter;
+pub mod linebreak;
 pub mod load;
+mod normalization;
+pub mod patgen;
+pub mod registry;
 #[cfg(any(feature = "embed_all", feature = "embed_en-us"))]
 mod resources;
 pub mod score;
+pub mod text;
+pub mod trace;
 
 pub use hyphenation_commons::dictionary::Standard;
-pub use hyphenation_commons::Language;
+pub use hyphenation_commons::parse;
+pub use hyphenation_commons::tag;
+pub use hyphenation_commons::{HyphenationOptions, Language, Normalization};
+pub use builder::DictionaryBuilder;
 pub use hyphenator::Hyphenator;
 pub use iter::Iter;
-pub use load::Load;
+pub use load::{Load, LoadBorrowed, Store};
+pub use text::TextHyphenator;
@@ -0,0 +1,786 @@
+/*! Optimal line breaking
+
+An implementation of the Knuth–Plass "total fit" algorithm[^1], which breaks
+a paragraph into lines by minimizing the *demerits* of the paragraph as a
+whole, rather than filling each line in turn.
+
+The paragraph is first described as a sequence of [`Item`]s:
+
+- *boxes*, unbreakable material of fixed width, such as word segments;
+- *glue*, elastic space between boxes, which can stretch or shrink;
+- *penalties*, potential breakpoints carrying a cost for breaking there.
+
+Hyphenation opportunities become *flagged* penalties, whose width is that of
+the hyphen that would be set should the line break there. Consecutive lines
+ending in flagged penalties incur additional demerits.
+
+```ignore
+use hyphenation::linebreak::{Chars, Paragraph, Parameters};
+
+let text = "I know noble accents / And lucid, inescapable rhythms";
+let paragraph = Paragraph::new(text, &en_us, &Chars, Parameters::default());
+for line in paragraph.lines(&[16.0]) {
+    println!("{}", line.text);
+}
+```
+
+Widths are expressed in whatever unit the [`Measure`] implementation
+prefers; [`Chars`] simply counts `char`s, which suits monospace text, while
+typesetting applications will want to supply font metrics.
+
+
+## First fit
+
+Where optimal breaks are not worth the trouble, as in terminals or plain-text
+email, [`first_fit`] wraps text greedily, hyphenating a word only when it
+does not fit on the current line.
+
+```ignore
+use hyphenation::linebreak::{first_fit, Chars};
+
+let lines = first_fit("Far out in the uncharted backwaters", &en_us, &Chars, 20.0, "-");
+assert_eq!(lines, vec!["Far out in the un-", "charted backwaters"]);
+```
+
+
+[^1]: Donald E. Knuth and Michael F. Plass, "Breaking Paragraphs into Lines", Software: Practice and Experience 11 (1981).
+
+[`Item`]: enum.Item.html
+[`Measure`]: trait.Measure.html
+[`Chars`]: struct.Chars.html
+[`first_fit`]: fn.first_fit.html
+*/
+
+use std::borrow::Cow;
+use std::ops::Range;
+
+use crate::discretionary::Discretionary;
+use crate::hyphenator::{Break, Hyphenator};
+use crate::text::Offset;
+
+
+/// The width of strings, in arbitrary units.
+pub trait Measure {
+    fn width(&self, s : &str) -> f64;
+}
+
+/// A measure of strings in `char`s, suitable for monospace text.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Chars;
+
+impl Measure for Chars {
+    #[inline]
+    fn width(&self, s : &str) -> f64 { s.chars().count() as f64 }
+}
+
+impl<F> Measure for F where F : Fn(&str) -> f64
+{
+    #[inline]
+    fn width(&self, s : &str) -> f64 { self(s) }
+}
+
+
+/// The elements of a paragraph, as understood by the line breaker.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Item {
+    /// Unbreakable material of fixed width.
+    Box { width : f64 },
+    /// Elastic space, which may be broken if it immediately follows a box.
+    Glue {
+        width :   f64,
+        stretch : f64,
+        shrink :  f64,
+    },
+    /// A potential breakpoint. The `width` is only set if the line breaks
+    /// here; an infinite penalty forbids the break, while a negative infinite
+    /// one forces it.
+    Penalty {
+        width :   f64,
+        penalty : f64,
+        flagged : bool,
+    },
+}
+
+impl Item {
+    /// The items that conclude every paragraph, filling the last line and
+    /// forcing a final break.
+    pub fn finishing() -> [Item; 3] {
+        [Item::Penalty { width :   0.0,
+                         penalty : f64::INFINITY,
+                         flagged : false, },
+         Item::Glue { width :   0.0,
+                      stretch : f64::INFINITY,
+                      shrink :  0.0, },
+         Item::Penalty { width :   0.0,
+                         penalty : f64::NEG_INFINITY,
+                         flagged : true, }]
+    }
+}
+
+
+/// Parameters governing the line breaker.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Parameters {
+    /// The maximum adjustment ratio of a line; that is, how far its glue may
+    /// stretch, relative to the total stretchability of the line. Should no
+    /// set of breaks satisfy the tolerance, it will be lifted, and lines as
+    /// loose as necessary will be set instead.
+    pub tolerance :        f64,
+    /// The demerits added to every line, to prefer paragraphs with fewer
+    /// lines.
+    pub line_penalty :     f64,
+    /// The penalty for breaking a line at a hyphenation opportunity.
+    pub hyphen_penalty :   f64,
+    /// The demerits for two consecutive lines ending at flagged penalties,
+    /// i.e. with a hyphen.
+    pub flagged_demerits : f64,
+    /// The demerits for adjacent lines whose tightness differs markedly.
+    pub fitness_demerits : f64,
+}
+
+impl Default for Parameters {
+    fn default() -> Self {
+        Parameters { tolerance :        2.0,
+                     line_penalty :     10.0,
+                     hyphen_penalty :   50.0,
+                     flagged_demerits : 3000.0,
+                     fitness_demerits : 100.0, }
+    }
+}
+
+
+/// A chosen breakpoint, with the adjustment ratio of the line it concludes.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Breakpoint {
+    /// The index of the item at which the line is broken.
+    pub position : usize,
+    /// How much the glue on the line must stretch (if positive) or shrink
+    /// (if negative), relative to its elasticity.
+    pub ratio :    f64,
+}
+
+/// Find the optimal breakpoints for a sequence of items. Lines take their
+/// widths in order from `widths`, the last of which applies to every
+/// subsequent line.
+///
+/// The items should end with the [finishing](enum.Item.html#method.finishing)
+/// items. Should some box be wider than the line, it will be set
+/// on an overfull line of its own.
+pub fn total_fit(items : &[Item], widths : &[f64], params : &Parameters) -> Vec<Breakpoint> {
+    assert!(!widths.is_empty(), "no line width was given");
+
+    Pass { items, widths, params }.run(params.tolerance, false)
+                                  .or_else(|| {
+                                      Pass { items, widths, params }.run(f64::INFINITY, true)
+                                  })
+                                  .unwrap_or_default()
+}
+
+
+#[derive(Copy, Clone, Debug, Default)]
+struct Sums {
+    width :   f64,
+    stretch : f64,
+    shrink :  f64,
+}
+
+#[derive(Clone, Debug)]
+struct Node {
+    position : usize,
+    line :     usize,
+    fitness :  usize,
+    flagged :  bool,
+    totals :   Sums,
+    demerits : f64,
+    ratio :    f64,
+    previous : Option<usize>,
+}
+
+#[derive(Clone, Debug)]
+struct Candidate {
+    demerits : f64,
+    ratio :    f64,
+    fitness :  usize,
+    line :     usize,
+    node :     usize,
+}
+
+struct Pass<'a> {
+    items :  &'a [Item],
+    widths : &'a [f64],
+    params : &'a Parameters,
+}
+
+impl<'a> Pass<'a> {
+    fn run(&self, tolerance : f64, emergency : bool) -> Option<Vec<Breakpoint>> {
+        let mut nodes = vec![Node { position : 0,
+                                    line :     0,
+                                    fitness :  1,
+                                    flagged :  false,
+                                    totals :   Sums::default(),
+                                    demerits : 0.0,
+                                    ratio :    0.0,
+                                    previous : None, }];
+        let mut active = vec![0];
+        let mut sums = Sums::default();
+
+        for (b, item) in self.items.iter().enumerate() {
+            match *item {
+                Item::Box { width } => sums.width += width,
+                Item::Glue { width, stretch, shrink } => {
+                    if b > 0 {
+                        if let Item::Box { .. } = self.items[b - 1] {
+                            self.try_break(b,
+                                           0.0,
+                                           0.0,
+                                           false,
+                                           &sums,
+                                           tolerance,
+                                           emergency,
+                                           &mut nodes,
+                                           &mut active);
+                        }
+                    }
+                    sums.width += width;
+                    sums.stretch += stretch;
+                    sums.shrink += shrink;
+                }
+                Item::Penalty { width, penalty, flagged } => {
+                    if penalty < f64::INFINITY {
+                        self.try_break(b,
+                                       width,
+                                       penalty,
+                                       flagged,
+                                       &sums,
+                                       tolerance,
+                                       emergency,
+                                       &mut nodes,
+                                       &mut active);
+                    }
+                }
+            }
+
+            if active.is_empty() {
+                return None;
+            }
+        }
+
+        let last = self.items.len().saturating_sub(1);
+        let best = active.iter()
+                         .filter(|&&a| nodes[a].position == last)
+                         .min_by(|&&a, &&z| nodes[a].demerits.total_cmp(&nodes[z].demerits))?;
+
+        let mut breaks = vec![];
+        let mut current = Some(*best);
+        while let Some(n) = current {
+            let node = &nodes[n];
+            if node.previous.is_some() {
+                breaks.push(Breakpoint { position : node.position,
+                                         ratio :    node.ratio, });
+            }
+            current = node.previous;
+        }
+        breaks.reverse();
+        Some(breaks)
+    }
+
+    fn line_width(&self, line : usize) -> f64 { self.widths[line.min(self.widths.len() - 1)] }
+
+    /// Lines beyond the last specified width are interchangeable.
+    fn line_class(&self, line : usize) -> usize { line.min(self.widths.len()) }
+
+    #[allow(clippy::too_many_arguments)]
+    fn try_break(&self,
+                 b : usize,
+                 width : f64,
+                 penalty : f64,
+                 flagged : bool,
+                 sums : &Sums,
+                 tolerance : f64,
+                 emergency : bool,
+                 nodes : &mut Vec<Node>,
+                 active : &mut Vec<usize>) {
+        let params = self.params;
+        let mut candidates : Vec<Candidate> = vec![];
+        let mut rescue : Option<(f64, Candidate)> = None;
+
+        active.retain(|&a| {
+                  let node = &nodes[a];
+                  let line_width = self.line_width(node.line);
+                  let natural = sums.width - node.totals.width + width;
+                  let ratio = if natural < line_width {
+                      let stretch = sums.stretch - node.totals.stretch;
+                      if stretch > 0.0 {
+                          (line_width - natural) / stretch
+                      } else {
+                          f64::INFINITY
+                      }
+                  } else if natural > line_width {
+                      let shrink = sums.shrink - node.totals.shrink;
+                      if shrink > 0.0 {
+                          (line_width - natural) / shrink
+                      } else {
+                          f64::NEG_INFINITY
+                      }
+                  } else {
+                      0.0
+                  };
+
+                  let feasible = ratio >= -1.0 && ratio <= tolerance;
+                  if feasible || (emergency && ratio < -1.0) {
+                      let badness = (100.0 * ratio.abs().powi(3)).min(10_000.0);
+                      let base = (params.line_penalty + badness).powi(2);
+                      let mut demerits = if penalty >= 0.0 {
+                          base + penalty.powi(2)
+                      } else if penalty > f64::NEG_INFINITY {
+                          base - penalty.powi(2)
+                      } else {
+                          base
+                      };
+                      if flagged && node.flagged {
+                          demerits += params.flagged_demerits;
+                      }
+                      let fitness = fitness_class(ratio);
+                      if (fitness as isize - node.fitness as isize).abs() > 1 {
+                          demerits += params.fitness_demerits;
+                      }
+
+                      let candidate = Candidate { demerits : node.demerits + demerits,
+                                                  ratio,
+                                                  fitness,
+                                                  line : node.line + 1,
+                                                  node : a };
+                      if feasible {
+                          let class = self.line_class(candidate.line);
+                          match candidates.iter_mut().find(|c| {
+                                                         c.fitness == fitness
+                                                         && self.line_class(c.line) == class
+                                                     }) {
+                              Some(c) if c.demerits <= candidate.demerits => (),
+                              Some(c) => *c = candidate,
+                              None => candidates.push(candidate),
+                          }
+                      } else {
+                          // Among overfull lines, prefer the one that overflows least.
+                          let overflow = natural - line_width;
+                          let better = rescue.as_ref()
+                                             .map_or(true, |(o, r)| {
+                                                 (overflow, candidate.demerits) < (*o, r.demerits)
+                                             });
+                          if better {
+                              rescue = Some((overflow, candidate));
+                          }
+                      }
+                  }
+
+                  ratio >= -1.0 && penalty > f64::NEG_INFINITY
+              });
+
+        // Should every line be overfull, set the least objectionable one
+        // rather than fail altogether.
+        if candidates.is_empty() && active.is_empty() {
+            candidates.extend(rescue.map(|(_, r)| r));
+        }
+
+        if candidates.is_empty() {
+            return;
+        }
+
+        // The totals from the breakpoint to the start of the next line, which
+        // discards any glue and penalties following the break.
+        let mut totals = *sums;
+        for (i, item) in self.items.iter().enumerate().skip(b) {
+            match *item {
+                Item::Box { .. } => break,
+                Item::Glue { width, stretch, shrink } => {
+                    totals.width += width;
+                    totals.stretch += stretch;
+                    totals.shrink += shrink;
+                }
+                Item::Penalty { penalty, .. } => {
+                    if penalty == f64::NEG_INFINITY && i > b {
+                        break;
+                    }
+                }
+            }
+        }
+
+        for c in candidates {
+            nodes.push(Node { position : b,
+                              line : c.line,
+                              fitness : c.fitness,
+                              flagged,
+                              totals,
+                              demerits : c.demerits,
+                              ratio : c.ratio,
+                              previous : Some(c.node) });
+            active.push(nodes.len() - 1);
+        }
+    }
+}
+
+/// Lines are classified as tight, decent, loose or very loose.
+fn fitness_class(ratio : f64) -> usize {
+    if ratio < -0.5 {
+        0
+    } else if ratio <= 0.5 {
+        1
+    } else if ratio <= 1.0 {
+        2
+    } else {
+        3
+    }
+}
+
+
+/// A line of text, as broken from a paragraph.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Line<'t> {
+    /// The text of the line, including the hyphen if it was broken at a
+    /// hyphenation opportunity, and the substitutions of breaks that alter
+    /// the word around them.
+    pub text :       Cow<'t, str>,
+    /// The byte range of the line within the paragraph text. Letters that a
+    /// break replaces with its substitutions lie outside either line.
+    pub range :      Range<usize>,
+    /// How much the spaces on the line must stretch (if positive) or shrink
+    /// (if negative), relative to their elasticity.
+    pub ratio :      f64,
+    /// Whether the line ends with a hyphen.
+    pub hyphenated : bool,
+}
+
+/// A paragraph of text, prepared for line breaking.
+#[derive(Clone, Debug)]
+pub struct Paragraph<'t, 'm> {
+    text :            &'t str,
+    mark :            &'m str,
+    items :           Vec<Item>,
+    positions :       Vec<Range<usize>>,
+    /// The text to be set before and after each hyphenation penalty, by the
+    /// position of the penalty among the items.
+    discretionaries : Vec<(usize, String, String)>,
+    params :          Parameters,
+}
+
+impl<'t, 'm> Paragraph<'t, 'm> {
+    /// Describe the text as a sequence of items, with the hyphenation
+    /// opportunities found by the given hyphenator.
+    ///
+    /// Runs of whitespace become glue, with the stretchability and
+    /// shrinkability customary in typesetting: one half and one third of a
+    /// space, respectively. Explicit hyphens are also valid breaks.
+    ///
+    /// Breaks that alter the word around them, as found by `Extended`
+    /// dictionaries, are set with their substitutions: the penalty is as wide
+    /// as the text before the break and the mark, and it is followed by
+    /// rigid glue, discarded if the line breaks there, which makes up the
+    /// difference between the unbroken text and the text after the break.
+    pub fn new<'h, H, M>(text : &'t str,
+                         hyphenator : &'h H,
+                         measure : &M,
+                         params : Parameters)
+                         -> Self
+        where H : Hyphenator<'h>,
+              H::Opportunity : Offset + Break,
+              M : Measure
+    {
+        Self::with_mark(text, hyphenator, measure, params, "-")
+    }
+
+    /// Prepare the paragraph as with `new`, setting the given mark instead of
+    /// a hyphen at hyphenation breaks.
+    pub fn with_mark<'h, H, M>(text : &'t str,
+                               hyphenator : &'h H,
+                               measure : &M,
+                               params : Parameters,
+                               mark : &'m str)
+                               -> Self
+        where H : Hyphenator<'h>,
+              H::Opportunity : Offset + Break,
+              M : Measure
+    {
+        let hyphenated = hyphenator.hyphenate_text(text);
+        let mut opportunities = hyphenated.discretionaries().into_iter().peekable();
+
+        let mut items = vec![];
+        let mut positions = vec![];
+        let mut discretionaries = vec![];
+        let space = measure.width(" ");
+        // The text after the last break, which begins the next box in place
+        // of the letters the break altered.
+        let mut carry = String::new();
+        let (mut start, mut resume, mut in_space) = (0, 0, true);
+
+        let push_box = |items : &mut Vec<Item>, positions : &mut Vec<_>, carry : &mut String, s, e| {
+            items.push(Item::Box { width : measure.width(&[carry.as_str(), &text[s .. e]].concat()), });
+            positions.push(s .. e);
+            carry.clear();
+        };
+
+        for (i, c) in text.char_indices().chain(Some((text.len(), ' '))) {
+            if i < resume {
+                continue;
+            }
+
+            if c.is_whitespace() {
+                if !in_space {
+                    push_box(&mut items, &mut positions, &mut carry, start, i);
+                    in_space = true;
+                    start = i;
+                }
+                continue;
+            }
+
+            if in_space {
+                if !items.is_empty() {
+                    items.push(Item::Glue { width :   space,
+                                            stretch : space / 2.0,
+                                            shrink :  space / 3.0, });
+                    positions.push(start .. i);
+                }
+                in_space = false;
+                start = i;
+            }
+
+            // Opportunities that start before the current box are passed over.
+            while opportunities.next_if(|d| d.span.start < i).is_some() {}
+            let material = start < i || !carry.is_empty();
+            if let Some(d) = opportunities.next_if(|d| d.span.start == i && material) {
+                push_box(&mut items, &mut positions, &mut carry, start, i);
+                items.push(Item::Penalty { width :   measure.width(&[d.pre_break, mark].concat()),
+                                           penalty : params.hyphen_penalty,
+                                           flagged : true, });
+                positions.push(d.span.clone());
+                discretionaries.push((items.len() - 1,
+                                      d.pre_break.to_owned(),
+                                      d.post_break.to_owned()));
+                if !d.is_plain() {
+                    let width = measure.width(d.no_break) - measure.width(d.post_break);
+                    items.push(Item::Glue { width,
+                                            stretch : 0.0,
+                                            shrink : 0.0 });
+                    positions.push(d.span.clone());
+                }
+                carry.push_str(d.post_break);
+                start = d.span.end;
+                resume = d.span.end;
+                if i < resume {
+                    continue;
+                }
+            }
+
+            let next = i + c.len_utf8();
+            let explicit = c == '-'
+                           && text[next ..].chars()
+                                           .next()
+                                           .is_some_and(char::is_alphabetic);
+            if explicit {
+                push_box(&mut items, &mut positions, &mut carry, start, next);
+                items.push(Item::Penalty { width :   0.0,
+                                           penalty : params.hyphen_penalty,
+                                           flagged : true, });
+                positions.push(next .. next);
+                start = next;
+            }
+        }
+
+        if !items.is_empty() {
+            items.extend_from_slice(&Item::finishing());
+            positions.extend(vec![text.len() .. text.len(); 3]);
+        }
+
+        Paragraph { text,
+                    mark,
+                    items,
+                    positions,
+                    discretionaries,
+                    params }
+    }
+
+    /// The items describing this paragraph.
+    pub fn items(&self) -> &[Item] { &self.items }
+
+    /// Break the paragraph into lines, taking their widths in order from
+    /// `widths`; the last width applies to every subsequent line.
+    pub fn lines(&self, widths : &[f64]) -> Vec<Line<'t>> {
+        let breaks = total_fit(&self.items, widths, &self.params);
+
+        let mut lines = Vec::with_capacity(breaks.len());
+        let (mut from, mut carry) = (0, "");
+        for Breakpoint { position, ratio } in breaks {
+            // Lines start at the first box following the previous break.
+            let first = (from .. position).find(|&i| matches!(self.items[i], Item::Box { .. }));
+            let start = first.map_or(self.positions[position].start, |i| self.positions[i].start);
+            let end = if position + 1 == self.items.len() {
+                let last = (from .. position).rev()
+                                             .find(|&i| matches!(self.items[i], Item::Box { .. }));
+                last.map_or(start, |i| self.positions[i].end)
+            } else {
+                self.positions[position].start
+            };
+            let discretionary = self.discretionaries
+                                    .binary_search_by_key(&position, |&(p, ..)| p)
+                                    .ok()
+                                    .map(|k| &self.discretionaries[k]);
+            let hyphenated = matches!(self.items[position], Item::Penalty { flagged : true, .. })
+                             && end < self.text.len();
+            let (pre, post, mark) = match discretionary {
+                Some((_, pre, post)) if hyphenated => (pre.as_str(), post.as_str(), self.mark),
+                _ => ("", "", ""),
+            };
+            let body = &self.text[start .. end];
+            let text = if carry.is_empty() && pre.is_empty() && mark.is_empty() {
+                Cow::Borrowed(body)
+            } else {
+                Cow::Owned([carry, body, pre, mark].concat())
+            };
+            carry = post;
+
+            lines.push(Line { text,
+                              range : start .. end,
+                              ratio,
+                              hyphenated });
+            from = position + 1;
+        }
+
+        lines
+    }
+}
+
+
+/// A break within a run of text, resolved against the text itself.
+#[derive(Clone, Debug)]
+struct Cut<'a> {
+    /// Where the text before the break ends.
+    pre_end :    usize,
+    /// Where the text after the break resumes.
+    post_start : usize,
+    /// Text set before the break, in place of the altered letters.
+    pre :        &'a str,
+    /// Text set after the break, in place of the altered letters.
+    post :       &'a str,
+    /// Whether the break calls for a mark, as opposed to an explicit hyphen.
+    marked :     bool,
+}
+
+/// Wrap text to the given width, filling each line with as many words as
+/// will fit before starting a new one.
+///
+/// Words are only hyphenated when they do not fit on the current line, at the
+/// last opportunity that leaves room for the mark. Should no opportunity fit,
+/// the word is moved to the next line instead; a word too wide for an empty
+/// line is broken at its first opportunity, and left to overflow if it has
+/// none. Breaks that alter the word, as found by `Extended` dictionaries, are
+/// set with their substitutions.
+///
+/// Runs of whitespace are collapsed into single spaces, except for newlines,
+/// which are kept as hard breaks.
+pub fn first_fit<'h, H, M>(text : &str,
+                           hyphenator : &'h H,
+                           measure : &M,
+                           width : f64,
+                           mark : &str)
+                           -> Vec<String>
+    where H : Hyphenator<'h>,
+          H::Opportunity : Offset + Break,
+          M : Measure
+{
+    let mut lines = vec![];
+    for paragraph in text.split('\n') {
+        let hyphenated = hyphenator.hyphenate_text(paragraph);
+        let mut line = String::new();
+
+        for (start, chunk) in chunks(paragraph) {
+            let end = start + chunk.len();
+            let cuts = cuts(paragraph, &hyphenated.breaks, start .. end);
+            let (mut carry, mut from) = ("", start);
+
+            loop {
+                let separator = if line.is_empty() { "" } else { " " };
+                let rest = [carry, &paragraph[from .. end]].concat();
+                if measure.width(&[&line, separator, &rest].concat()) <= width {
+                    line.push_str(separator);
+                    line.push_str(&rest);
+                    break;
+                }
+
+                let piece = |c : &Cut| {
+                    let mark = if c.marked { mark } else { "" };
+                    [separator, carry, &paragraph[from .. c.pre_end], c.pre, mark].concat()
+                };
+                let remaining = cuts.iter().filter(|c| c.pre_end > from);
+                let cut = match remaining.clone().rev().find(|&c| {
+                                                           measure.width(&[line.as_str(),
+                                                                           &piece(c)].concat())
+                                                           <= width
+                                                       }) {
+                    Some(c) => c,
+                    None if !line.is_empty() => {
+                        lines.push(std::mem::take(&mut line));
+                        continue;
+                    }
+                    None => match remaining.clone().next() {
+                        Some(c) => c,
+                        None => {
+                            line = rest;
+                            break;
+                        }
+                    },
+                };
+
+                line.push_str(&piece(cut));
+                lines.push(std::mem::take(&mut line));
+                carry = cut.post;
+                from = cut.post_start;
+            }
+        }
+
+        lines.push(line);
+    }
+
+    lines
+}
+
+/// The runs of non-whitespace characters in the text, with their byte index.
+fn chunks(text : &str) -> impl Iterator<Item = (usize, &str)> {
+    text.split(char::is_whitespace)
+        .scan(0, move |start, chunk| {
+            let i = *start;
+            *start += chunk.len()
+                      + text[*start + chunk.len() ..].chars()
+                                                     .next()
+                                                     .map_or(0, char::len_utf8);
+            Some((i, chunk))
+        })
+        .filter(|(_, chunk)| !chunk.is_empty())
+}
+
+/// The hyphenation opportunities and explicit hyphens within the given range.
+fn cuts<'a, B>(text : &'a str, breaks : &'a [B], range : Range<usize>) -> Vec<Cut<'a>>
+    where B : Break
+{
+    let hyphenation = breaks.iter()
+                            .filter(|b| b.index() > range.start && b.index() < range.end)
+                            .map(|b| {
+                                let d = Discretionary::new(text, b);
+                                Cut { pre_end :    d.span.start,
+                                      post_start : d.span.end,
+                                      pre :        d.pre_break,
+                                      post :       d.post_break,
+                                      marked :     true, }
+                            });
+    let explicit =
+        text[range.clone()].match_indices('-')
+                           .map(|(i, _)| range.start + i + 1)
+                           .filter(|&i| text[i ..].chars().next().is_some_and(char::is_alphabetic))
+                           .map(|i| Cut { pre_end :    i,
+                                          post_start : i,
+                                          pre :        "",
+                                          post :       "",
+                                          marked :     false, });
+
+    let mut cuts : Vec<_> = hyphenation.chain(explicit).collect();
+    cuts.sort_by_key(|c| c.pre_end);
+    cuts
+}
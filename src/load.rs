@@ -56,8 +56,62 @@ let english_us = Standard::from_embedded(Language::EnglishUS) ?;
 Note that embedding significantly increases the size of the compiled artifact.
 
 
+## Zero-copy loading
+
+Deserializing a dictionary with [`Load`] copies its pattern trie into memory
+that it owns. When loading many dictionaries at once, the copy can be avoided
+with [`LoadBorrowed`], whose dictionaries borrow the trie from a byte buffer
+for as long as they live. The buffer may well be a memory-mapped file:
+
+```ignore
+use hyphenation::load::LoadBorrowed;
+use memmap2::Mmap;
+
+let file = File::open("/path/to/english-dictionary.bincode") ?;
+let bytes = unsafe { Mmap::map(&file) ? };
+let english_us : Standard<&[u8]> = Standard::from_bytes(Language::EnglishUS, &bytes) ?;
+```
+
+Embedded dictionaries can likewise be borrowed from the compiled artifact,
+with [`from_embedded_bytes`].
+
+
+## Storing
+
+Conversely, dictionaries built at runtime can be serialized with the
+[`Store`] trait, to be loaded again later.
+
+```ignore
+use hyphenation::load::Store;
+
+dictionary.to_path("/path/to/dictionary.bincode") ?;
+```
+
+
+## File format
+
+Dictionary files open with a [`Header`] naming the format version, the kind
+and language of the dictionary, and the version of the patterns it was built
+from, followed by a checksum of the dictionary itself. Loading verifies all
+of these, so that a dictionary from an incompatible version of this crate,
+of the wrong kind or language, or damaged on disk, is reported as such
+rather than misread. The header can also be inspected on its own:
+
+```ignore
+use hyphenation::load::Header;
+
+let bytes = std::fs::read("/path/to/english-dictionary.bincode") ?;
+let (header, _) = Header::read(&bytes) ?;
+println!("{} ({}), from {}", header.language, header.kind, header.source);
+```
+
+
 [`Load`]: trait.Load.html
 [`from_path`]: trait.Load.html#method.from_path
+[`Store`]: trait.Store.html
+[`LoadBorrowed`]: trait.LoadBorrowed.html
+[`from_embedded_bytes`]: trait.LoadBorrowed.html#method.from_embedded_bytes
+[`Header`]: struct.Header.html
 */
 
 use bincode as bin;
@@ -71,6 +125,8 @@ use std::path::Path;
 use std::result;
 
 use hyphenation_commons::dictionary::{extended::Extended, Standard};
+use hyphenation_commons::header;
+pub use hyphenation_commons::header::{Header, Kind};
 use hyphenation_commons::Language;
 
 
@@ -100,39 +156,146 @@ pub trait Load: Sized {
 }
 
 macro_rules! impl_load {
-    ($dict:ty, $suffix:expr) => {
+    ($dict:ty, $suffix:expr, $kind:expr) => {
         impl Load for $dict {
             fn from_reader<R>(lang : Language, reader : &mut R) -> Result<Self>
                 where R : io::Read
             {
-                let dict : Self = bin::deserialize_from(reader)?;
-                let (found, expected) = (dict.language(), lang);
-                if found != expected {
-                    Err(Error::LanguageMismatch { expected, found })
-                } else {
-                    Ok(dict)
-                }
+                let (header, body) = Header::read_from(reader)?;
+                verify(&header, $kind, Some(lang))?;
+                let dict : Self = bin::deserialize(&body)?;
+                Ok(dict)
             }
 
             fn any_from_reader<R>(reader : &mut R) -> Result<Self>
                 where R : io::Read
             {
-                let dict : Self = bin::deserialize_from(reader)?;
+                let (header, body) = Header::read_from(reader)?;
+                verify(&header, $kind, None)?;
+                let dict : Self = bin::deserialize(&body)?;
                 Ok(dict)
             }
 
             #[cfg(any(feature = "embed_all", feature = "embed_en-us"))]
             fn from_embedded(lang : Language) -> Result<Self> {
                 let dict_bytes = retrieve_resource(lang.code(), $suffix)?;
-                let dict = bin::deserialize(dict_bytes)?;
+                let (header, body) = Header::read(dict_bytes)?;
+                verify(&header, $kind, Some(lang))?;
+                let dict = bin::deserialize(body)?;
+                Ok(dict)
+            }
+        }
+    };
+}
+
+impl_load! { Standard, "standard", Kind::Standard }
+impl_load! { Extended, "extended", Kind::Extended }
+
+
+/// Zero-copy retrieval of hyphenation dictionaries, whose pattern trie is
+/// borrowed from a byte buffer rather than copied out of it.
+pub trait LoadBorrowed<'b>: Sized {
+    /// Deserialize a dictionary from the provided bytes, verifying that it
+    /// belongs to the expected language.
+    fn from_bytes(lang : Language, bytes : &'b [u8]) -> Result<Self>;
+
+    /// Deserialize a dictionary from the provided bytes.
+    fn any_from_bytes(bytes : &'b [u8]) -> Result<Self>;
+
+    #[cfg(any(feature = "embed_all", feature = "embed_en-us"))]
+    /// Borrow the embedded dictionary for the given language.
+    fn from_embedded_bytes(lang : Language) -> Result<Self>;
+}
+
+macro_rules! impl_load_borrowed {
+    ($dict:ident, $suffix:expr, $kind:expr) => {
+        impl<'b> LoadBorrowed<'b> for $dict<&'b [u8]> {
+            fn from_bytes(lang : Language, bytes : &'b [u8]) -> Result<Self> {
+                let (header, body) = Header::read(bytes)?;
+                verify(&header, $kind, Some(lang))?;
+                let dict : Self = bin::deserialize(body)?;
                 Ok(dict)
             }
+
+            fn any_from_bytes(bytes : &'b [u8]) -> Result<Self> {
+                let (header, body) = Header::read(bytes)?;
+                verify(&header, $kind, None)?;
+                let dict : Self = bin::deserialize(body)?;
+                Ok(dict)
+            }
+
+            #[cfg(any(feature = "embed_all", feature = "embed_en-us"))]
+            fn from_embedded_bytes(lang : Language) -> Result<Self> {
+                let dict_bytes = retrieve_resource(lang.code(), $suffix)?;
+                Self::from_bytes(lang, dict_bytes)
+            }
+        }
+    };
+}
+
+impl_load_borrowed! { Standard, "standard", Kind::Standard }
+impl_load_borrowed! { Extended, "extended", Kind::Extended }
+
+
+/// Verify that the header describes a dictionary of the expected kind and,
+/// if given, of the expected language.
+fn verify(header : &Header, kind : Kind, lang : Option<Language>) -> Result<()> {
+    if header.kind != kind {
+        return Err(Error::KindMismatch { expected : kind,
+                                         found :    header.kind, });
+    }
+    match lang {
+        Some(expected) if expected != header.language => {
+            Err(Error::LanguageMismatch { expected,
+                                          found : header.language })
+        }
+        _ => Ok(()),
+    }
+}
+
+
+/// Serialization of hyphenation dictionaries, in the form read by `Load`.
+pub trait Store {
+    /// Serialize the dictionary to the provided writer, recording the version
+    /// of the patterns it was built from in its header.
+    fn to_writer_with_source<W>(&self, source : &str, writer : &mut W) -> Result<()>
+        where W : io::Write;
+
+    /// Serialize the dictionary to the provided writer.
+    fn to_writer<W>(&self, writer : &mut W) -> Result<()>
+        where W : io::Write
+    {
+        self.to_writer_with_source("", writer)
+    }
+
+    /// Serialize the dictionary to a file at the given path, creating it if
+    /// necessary.
+    fn to_path<P>(&self, path : P) -> Result<()>
+        where P : AsRef<Path>
+    {
+        let mut writer = io::BufWriter::new(File::create(path)?);
+        self.to_writer(&mut writer)?;
+        io::Write::flush(&mut writer)?;
+        Ok(())
+    }
+}
+
+macro_rules! impl_store {
+    ($dict:ident, $kind:expr) => {
+        impl<D : AsRef<[u8]>> Store for $dict<D> {
+            fn to_writer_with_source<W>(&self, source : &str, writer : &mut W) -> Result<()>
+                where W : io::Write
+            {
+                let body = bin::serialize(self).map_err(Error::Serialization)?;
+                Header::new($kind, self.language(), source).write(writer, &body)?;
+                Ok(())
+            }
         }
     };
 }
 
-impl_load! { Standard, "standard" }
-impl_load! { Extended, "extended" }
+impl_store! { Standard, Kind::Standard }
+impl_store! { Extended, Kind::Extended }
 
 
 #[cfg(any(feature = "embed_all", feature = "embed_en-us"))]
@@ -153,15 +316,24 @@ pub type Result<T> = result::Result<T, Error>;
 pub enum Error {
     /// The dictionary could not be deserialized.
     Deserialization(bin::Error),
-    /// The dictionary could not be read.
+    /// The dictionary could not be read or written.
     IO(io::Error),
     /// The loaded dictionary is for the wrong language.
     LanguageMismatch {
         expected : Language,
         found :    Language,
     },
+    /// The loaded dictionary is of the wrong kind.
+    KindMismatch { expected : Kind, found : Kind },
+    /// The dictionary was written in a format version that this crate cannot
+    /// read.
+    VersionMismatch { found : u16, supported : u16 },
+    /// The dictionary is not a valid dictionary file, or was damaged.
+    Corrupted(header::Error),
     /// The embedded dictionary could not be retrieved.
     Resource,
+    /// The dictionary could not be serialized.
+    Serialization(bin::Error),
 }
 
 impl error::Error for Error {
@@ -169,6 +341,8 @@ impl error::Error for Error {
         match *self {
             Error::Deserialization(ref e) => Some(e),
             Error::IO(ref e) => Some(e),
+            Error::Corrupted(ref e) => Some(e),
+            Error::Serialization(ref e) => Some(e),
             _ => None,
         }
     }
@@ -186,7 +360,18 @@ Language mismatch: attempted to load a dictionary for `{}`, but found
 a dictionary for `{}` instead.",
                                                                   expected, found
             ),
+            Error::KindMismatch { expected, found } => write!(f,
+                                                              "attempted to load a dictionary \
+                                                               of kind `{}`, but found kind `{}`",
+                                                              expected, found),
+            Error::VersionMismatch { found, supported } => {
+                write!(f,
+                       "unsupported dictionary format version {} (expected {})",
+                       found, supported)
+            }
+            Error::Corrupted(ref e) => e.fmt(f),
             Error::Resource => f.write_str("the embedded dictionary could not be retrieved"),
+            Error::Serialization(ref e) => e.fmt(f),
         }
     }
 }
@@ -195,6 +380,18 @@ impl From<io::Error> for Error {
     fn from(err : io::Error) -> Error { Error::IO(err) }
 }
 
+impl From<header::Error> for Error {
+    fn from(err : header::Error) -> Error {
+        match err {
+            header::Error::Version(found) => Error::VersionMismatch { found,
+                                                                      supported :
+                                                                          header::VERSION },
+            header::Error::IO(e) => Error::IO(e),
+            e => Error::Corrupted(e),
+        }
+    }
+}
+
 impl From<bin::Error> for Error {
     fn from(err : bin::Error) -> Error { Error::Deserialization(err) }
 }
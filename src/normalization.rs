@@ -0,0 +1,82 @@
+/*!
+Normalization of words to the form of a dictionary's patterns
+
+Dictionaries record the Unicode normalization form of their patterns, and
+words are normalized likewise before hyphenation: otherwise, a word in NFD,
+such as "caf\u{65}\u{301}", would not match patterns in NFC, which expect
+"caf\u{e9}".
+
+Normalization may change the length of a word, and thus the indices of its
+breaks; the spans of the word that were altered are kept, so that breaks
+found in the normalized word can be realigned with the original.
+
+Words are divided where they may be normalized piecewise; each piece is
+then normalized on its own, and, should its characters normalize one by one,
+they are aligned one by one. Otherwise, as when NFC composes "e\u{301}" into
+"é", the piece is aligned as a whole, and breaks within it are moved to its
+start. Such pieces are single graphemes, or nearly so; patterns seldom break
+them.
+*/
+
+use std::borrow::Cow;
+
+use hyphenation_commons::Normalization;
+
+use crate::alignment::Span;
+
+
+/// Should the original string not be in the given normalization form,
+/// normalize it and provide the spans it altered. Otherwise, merely borrow
+/// it.
+pub fn normalize(form : Normalization, original : &str) -> (Cow<'_, str>, Vec<Span>) {
+    if let Cow::Borrowed(_) = form.apply(original) {
+        return (Cow::Borrowed(original), vec![]);
+    }
+
+    let mut normalized = String::with_capacity(original.len());
+    let mut spans = vec![];
+    for (i, piece) in pieces(form, original) {
+        let whole = form.apply(piece);
+        if whole == piece {
+            normalized.push_str(piece);
+            continue;
+        }
+
+        let singles : Vec<_> = piece.char_indices()
+                                    .map(|(j, c)| {
+                                        let c = &piece[j .. j + c.len_utf8()];
+                                        (i + j, c, form.apply(c))
+                                    })
+                                    .collect();
+        if singles.iter().map(|(_, _, n)| n.as_ref()).collect::<String>() == whole {
+            for (j, c, n) in singles {
+                if n != c {
+                    spans.push(Span { start :    normalized.len(),
+                                      end :      normalized.len() + n.len(),
+                                      original : (j, j + c.len()), });
+                }
+                normalized.push_str(&n);
+            }
+        } else {
+            spans.push(Span { start :    normalized.len(),
+                              end :      normalized.len() + whole.len(),
+                              original : (i, i + piece.len()), });
+            normalized.push_str(&whole);
+        }
+    }
+
+    (Cow::Owned(normalized), spans)
+}
+
+/// The pieces of the string that may be normalized on their own, paired with
+/// their byte index.
+fn pieces(form : Normalization, s : &str) -> impl Iterator<Item = (usize, &str)> {
+    let mut bounds : Vec<usize> = s.char_indices()
+                                   .skip(1)
+                                   .filter(|&(_, c)| form.is_boundary_before(c))
+                                   .map(|(i, _)| i)
+                                   .collect();
+    bounds.push(s.len());
+    let starts = Some(0).into_iter().chain(bounds.clone());
+    starts.zip(bounds).map(move |(start, end)| (start, &s[start .. end]))
+}
@@ -0,0 +1,261 @@
+/*! # Generating patterns from hyphenated words
+
+Hyphenation patterns are not written by hand, but learned from a list of
+correctly hyphenated words, as first described by Liang and implemented in
+TeX's `patgen`. This module offers the same procedure, so that dictionaries
+may be trained for languages or vocabularies for which no patterns exist.
+
+```
+use hyphenation::patgen::{self, Parameters};
+use hyphenation::{DictionaryBuilder, Hyphenator, Language};
+
+let words = ["hy-phen-ation", "hy-phen", "ta-ble", "ta-bles", "fa-ble", "sta-ble"];
+let patterns = patgen::generate(words.iter(), &Parameters::default());
+
+let dictionary = DictionaryBuilder::new(Language::EnglishUS)
+    .patterns(patterns)
+    .standard()?;
+assert_eq!(dictionary.hyphenate("table").breaks, &[2]);
+# Ok::<(), hyphenation::builder::Error>(())
+```
+
+Generation proceeds in *levels*. Odd levels learn patterns that mark breaks,
+and even levels learn patterns that inhibit the incorrect breaks marked by the
+previous level, each refining the last. At every level, patterns of increasing
+length are considered in turn, and a candidate is retained when it corrects
+enough of the words in the list without spoiling too many others: that is,
+when
+
+```text
+good × good_weight − bad × bad_weight ≥ threshold
+```
+
+The patterns are emitted in Knuth–Liang notation, such as `hy3ph`, ready to be
+given to a [`DictionaryBuilder`], written to a pattern file, or parsed with
+`Patterns::pair` for `Patterns::from_iter`.
+
+Words are normalized and case-folded before training, as dictionaries prepare
+the words they hyphenate: `Parameters::normalization` and `Parameters::folding`
+should match those of the dictionary to be built, lest the patterns learned
+fail to match its words, as with the dotless *ı* of Turkish.
+
+
+[`DictionaryBuilder`]: ../builder/struct.DictionaryBuilder.html
+*/
+
+use std::collections::HashMap;
+use std::ops::RangeInclusive;
+
+use hyphenation_commons::{caseless, Folding, Normalization};
+
+
+/// The parameters of a single level of pattern generation.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Level {
+    /// The lengths of the patterns to consider, in `char`s.
+    pub lengths :     RangeInclusive<usize>,
+    /// The weight of a break correctly marked, or inhibited, by a pattern.
+    pub good_weight : usize,
+    /// The weight of a break incorrectly marked, or inhibited, by a pattern.
+    pub bad_weight :  usize,
+    /// The minimum weighted score for a pattern to be retained.
+    pub threshold :   usize,
+}
+
+/// The parameters of pattern generation.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Parameters {
+    /// The parameters of each level, in order. Patterns can hold values from 1
+    /// to 9, so at most nine levels are generated.
+    pub levels :        Vec<Level>,
+    /// The minimum number of `char`s from the start and end of a word where
+    /// breaks should not be learned.
+    pub minima :        (usize, usize),
+    /// The normalization form of the dictionary for which patterns are
+    /// generated, to which words are normalized before training.
+    pub normalization : Normalization,
+    /// The case folding of the language for which patterns are generated,
+    /// as given by `Language::folding`, by which words are folded before
+    /// training.
+    pub folding :       Folding,
+}
+
+impl Default for Parameters {
+    fn default() -> Self {
+        let level = |lengths, good_weight, bad_weight, threshold| Level { lengths,
+                                                                          good_weight,
+                                                                          bad_weight,
+                                                                          threshold };
+        Parameters { levels :        vec![level(2 ..= 5, 1, 1, 1),
+                                          level(2 ..= 5, 1, 1, 1),
+                                          level(3 ..= 6, 1, 1, 1),
+                                          level(3 ..= 7, 1, 1, 1)],
+                     minima :        (1, 1),
+                     normalization : Normalization::None,
+                     folding :       Folding::Default, }
+    }
+}
+
+
+/// Generate patterns from a list of words, hyphenated with `-`, such as
+/// `hy-phen-ation`.
+pub fn generate<I, S>(words : I, parameters : &Parameters) -> Vec<String>
+    where I : IntoIterator<Item = S>,
+          S : AsRef<str>
+{
+    let (form, folding) = (parameters.normalization, parameters.folding);
+    let samples : Vec<_> = words.into_iter()
+                                .map(|w| Sample::new(w.as_ref(), form, folding))
+                                .collect();
+    let mut patterns = Patterns::default();
+
+    for (l, level) in parameters.levels.iter().take(9).enumerate() {
+        let value = l as u8 + 1;
+        for length in level.lengths.clone().filter(|&n| n > 0) {
+            let candidates = candidates(&samples, &patterns, value, length, parameters.minima);
+            for ((chars, k), (good, bad)) in candidates {
+                let score = (good * level.good_weight) as isize - (bad * level.bad_weight) as isize;
+                if good > 0 && score >= level.threshold as isize {
+                    patterns.insert(chars, k, value);
+                }
+            }
+        }
+    }
+
+    patterns.to_strings()
+}
+
+
+/// A hyphenated word, enclosed in the dots that mark word boundaries.
+#[derive(Clone, Debug)]
+struct Sample {
+    chars :  Vec<char>,
+    /// Whether each index, counting from the start of `chars`, is a break.
+    breaks : Vec<bool>,
+}
+
+impl Sample {
+    /// Each part of the word between hyphens is prepared as the dictionary
+    /// prepares words, so that the patterns learned match them.
+    fn new(hyphenated : &str, normalization : Normalization, folding : Folding) -> Self {
+        let mut chars = vec!['.'];
+        let mut breaks = vec![false; 2];
+        for (n, part) in hyphenated.trim().split('-').enumerate() {
+            if n > 0 {
+                breaks[chars.len()] = true;
+            }
+            chars.extend(caseless(part, normalization, folding).chars());
+            breaks.resize(chars.len() + 1, false);
+        }
+        chars.push('.');
+        breaks.resize(chars.len() + 1, false);
+        // A hyphen leading the word cannot be a break.
+        breaks[1] = false;
+
+        Sample { chars, breaks }
+    }
+
+    /// The indices between letters where breaks may be learned.
+    fn positions(&self, (left, right) : (usize, usize)) -> impl Iterator<Item = usize> {
+        let letters = self.chars.len() - 2;
+        left.max(1) + 1 ..= letters.saturating_sub(right.max(1)) + 1
+    }
+}
+
+
+/// The patterns learned so far, each mapped to the value it assigns to every
+/// index, from before its first `char` to after its last.
+#[derive(Clone, Debug, Default)]
+struct Patterns {
+    values :     HashMap<Vec<char>, Vec<u8>>,
+    max_length : usize,
+}
+
+impl Patterns {
+    fn insert(&mut self, chars : Vec<char>, k : usize, value : u8) {
+        self.max_length = self.max_length.max(chars.len());
+        let length = chars.len();
+        let values = self.values
+                         .entry(chars)
+                         .or_insert_with(|| vec![0; length + 1]);
+        values[k] = values[k].max(value);
+    }
+
+    /// The value of each index in the sample, as assigned by the patterns.
+    fn score(&self, sample : &Sample) -> Vec<u8> {
+        let chars = &sample.chars;
+        let mut score = vec![0; chars.len() + 1];
+        for start in 0 .. chars.len() {
+            let longest = self.max_length.min(chars.len() - start);
+            for end in start + 1 ..= start + longest {
+                if let Some(values) = self.values.get(&chars[start .. end]) {
+                    for (k, &v) in values.iter().enumerate() {
+                        score[start + k] = score[start + k].max(v);
+                    }
+                }
+            }
+        }
+
+        score
+    }
+
+    fn to_strings(&self) -> Vec<String> {
+        let mut strings : Vec<String> = self.values
+                                            .iter()
+                                            .map(|(chars, values)| {
+                                                let mut s = String::new();
+                                                for (k, &v) in values.iter().enumerate() {
+                                                    if v > 0 {
+                                                        s.push(char::from(b'0' + v));
+                                                    }
+                                                    if let Some(&c) = chars.get(k) {
+                                                        s.push(c);
+                                                    }
+                                                }
+                                                s
+                                            })
+                                            .collect();
+        strings.sort();
+        strings
+    }
+}
+
+
+/// Candidate patterns of the given length, keyed by their `char`s and the
+/// index they would assign `value` to, and paired with the number of good and
+/// bad breaks they would yield.
+fn candidates(samples : &[Sample],
+              patterns : &Patterns,
+              value : u8,
+              length : usize,
+              minima : (usize, usize))
+              -> HashMap<(Vec<char>, usize), (usize, usize)> {
+    let marks_breaks = value % 2 == 1;
+    let mut counts : HashMap<(Vec<char>, usize), (usize, usize)> = HashMap::new();
+
+    for sample in samples.iter().filter(|s| s.chars.len() >= length) {
+        let score = patterns.score(sample);
+        for p in sample.positions(minima) {
+            // Only indices that this level would change are of interest: at odd
+            // levels, those not yet marked as breaks; at even levels, those
+            // that are.
+            if (score[p] % 2 == 1) == marks_breaks {
+                continue;
+            }
+            let good = sample.breaks[p] == marks_breaks;
+            let first = p.saturating_sub(length);
+            let last = p.min(sample.chars.len() - length);
+            for start in first ..= last {
+                let key = (sample.chars[start .. start + length].to_vec(), p - start);
+                let count = counts.entry(key).or_insert((0, 0));
+                if good {
+                    count.0 += 1;
+                } else {
+                    count.1 += 1;
+                }
+            }
+        }
+    }
+
+    counts
+}
@@ -0,0 +1,264 @@
+/*! A registry of dictionaries for many languages
+
+Applications serving text in many languages need dictionaries for all of
+them, but rarely all at once. A [`Registry`] loads each dictionary when it is
+first requested, and keeps it thereafter, shared among threads:
+
+```ignore
+use hyphenation::registry::Registry;
+
+let registry : Registry<Standard> = Registry::new().directory("/path/to/dictionaries");
+
+let en_us = registry.get(Language::EnglishUS) ?;
+let de = registry.resolve("de-AT") ?;
+assert_eq!(de.language(), Language::German1996);
+```
+
+Dictionaries are sought from each of the registry's [`Provider`]s in turn: a
+directory of dictionary files, named after their language and kind as in
+`en-us.standard.bincode`; the dictionaries embedded in the compiled artifact,
+if any; or any function from a [`Language`] to a dictionary.
+
+
+## Language tags
+
+[`resolve`] finds the language that best serves a BCP 47 tag, as described in
+the [`tag`] module. Thus, `en` is resolved to `en-us`, `de-AT` to `de-1996`,
+`sr-Latn` to `sh-latn`, and `no` to `nb`.
+
+
+[`Registry`]: struct.Registry.html
+[`Provider`]: trait.Provider.html
+[`Language`]: ../enum.Language.html
+[`resolve`]: fn.resolve.html
+[`tag`]: ../tag/index.html
+*/
+
+use std::collections::HashMap;
+use std::error;
+use std::fmt;
+use std::path::PathBuf;
+use std::sync::{Arc, RwLock};
+
+use hyphenation_commons::dictionary::{extended::Extended, Standard};
+use hyphenation_commons::{tag, Language};
+
+use crate::load::{self, Kind, Load};
+
+
+/// Dictionaries that can be held in a registry.
+pub trait Dictionary: Load + Send + Sync {
+    /// The kind of dictionary, which names its files.
+    const KIND : Kind;
+}
+
+impl Dictionary for Standard {
+    const KIND : Kind = Kind::Standard;
+}
+
+impl Dictionary for Extended {
+    const KIND : Kind = Kind::Extended;
+}
+
+
+/// A source of dictionaries.
+pub trait Provider<H> {
+    /// Provide the dictionary for the given language, or `None` if this
+    /// provider has none.
+    fn provide(&self, lang : Language) -> Option<load::Result<H>>;
+}
+
+impl<H, F> Provider<H> for F where F : Fn(Language) -> Option<load::Result<H>>
+{
+    fn provide(&self, lang : Language) -> Option<load::Result<H>> { self(lang) }
+}
+
+/// A directory of dictionary files, named after their language and kind, as
+/// in `en-us.standard.bincode`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Directory(pub PathBuf);
+
+impl<H : Dictionary> Provider<H> for Directory {
+    fn provide(&self, lang : Language) -> Option<load::Result<H>> {
+        let path = self.0.join(format!("{}.{}.bincode", lang.code(), H::KIND));
+        if path.is_file() {
+            Some(H::from_path(lang, path))
+        } else {
+            None
+        }
+    }
+}
+
+/// The dictionaries embedded in the compiled artifact.
+#[cfg(any(feature = "embed_all", feature = "embed_en-us"))]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Embedded;
+
+#[cfg(any(feature = "embed_all", feature = "embed_en-us"))]
+impl<H : Dictionary> Provider<H> for Embedded {
+    fn provide(&self, lang : Language) -> Option<load::Result<H>> {
+        match H::from_embedded(lang) {
+            Err(load::Error::Resource) => None,
+            result => Some(result),
+        }
+    }
+}
+
+
+/// Dictionaries for many languages, loaded as they are first requested.
+///
+/// Loaded dictionaries are kept for the lifetime of the registry. Should two
+/// threads request the same dictionary before it is loaded, both may load it,
+/// but only one copy is kept.
+pub struct Registry<H = Standard> {
+    providers : Vec<Box<dyn Provider<H> + Send + Sync>>,
+    loaded :    RwLock<HashMap<Language, Arc<H>>>,
+}
+
+impl<H : Dictionary> Registry<H> {
+    /// An empty registry, without any provider.
+    pub fn new() -> Self {
+        Registry { providers : vec![],
+                   loaded :    RwLock::new(HashMap::new()), }
+    }
+
+    /// Seek dictionaries in the given directory.
+    pub fn directory<P>(self, path : P) -> Self
+        where P : Into<PathBuf>
+    {
+        self.provider(Directory(path.into()))
+    }
+
+    /// Seek dictionaries among those embedded in the compiled artifact.
+    #[cfg(any(feature = "embed_all", feature = "embed_en-us"))]
+    pub fn embedded(self) -> Self { self.provider(Embedded) }
+
+    /// Seek dictionaries from the given provider, after those given before.
+    pub fn provider<P>(mut self, provider : P) -> Self
+        where P : Provider<H> + Send + Sync + 'static
+    {
+        self.providers.push(Box::new(provider));
+        self
+    }
+
+    /// Keep the given dictionary for its language, replacing any other.
+    pub fn insert(&self, lang : Language, dictionary : H) -> Arc<H> {
+        let dictionary = Arc::new(dictionary);
+        self.write().insert(lang, Arc::clone(&dictionary));
+        dictionary
+    }
+
+    /// The dictionary for the given language, loaded from the first provider
+    /// that has one, unless it was loaded already.
+    pub fn get(&self, lang : Language) -> Result<Arc<H>, Error> {
+        if let Some(dictionary) = self.read().get(&lang) {
+            return Ok(Arc::clone(dictionary));
+        }
+
+        let dictionary = self.providers
+                             .iter()
+                             .find_map(|p| p.provide(lang))
+                             .ok_or(Error::Unavailable(lang))?
+                             .map_err(|e| Error::Load(lang, e))?;
+        let mut loaded = self.write();
+        Ok(Arc::clone(loaded.entry(lang).or_insert_with(|| Arc::new(dictionary))))
+    }
+
+    /// The dictionary for the language that best serves the given BCP 47
+    /// tag, as found by [`resolve`].
+    ///
+    /// [`resolve`]: fn.resolve.html
+    pub fn resolve(&self, tag : &str) -> Result<Arc<H>, Error> {
+        self.get(resolve(tag)?)
+    }
+
+    /// The languages whose dictionaries were loaded so far.
+    pub fn loaded(&self) -> Vec<Language> { self.read().keys().cloned().collect() }
+
+    // A panic while the lock is held cannot leave the map inconsistent, so a
+    // poisoned lock is of no concern.
+    fn read(&self) -> std::sync::RwLockReadGuard<'_, HashMap<Language, Arc<H>>> {
+        self.loaded.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn write(&self) -> std::sync::RwLockWriteGuard<'_, HashMap<Language, Arc<H>>> {
+        self.loaded.write().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl<H : Dictionary> Default for Registry<H> {
+    fn default() -> Self { Self::new() }
+}
+
+impl<H> fmt::Debug for Registry<H> {
+    fn fmt(&self, f : &mut fmt::Formatter) -> fmt::Result {
+        let loaded : Vec<Language> = match self.loaded.read() {
+            Ok(loaded) => loaded.keys().cloned().collect(),
+            Err(e) => e.into_inner().keys().cloned().collect(),
+        };
+        f.debug_struct("Registry")
+         .field("providers", &self.providers.len())
+         .field("loaded", &loaded)
+         .finish()
+    }
+}
+
+
+/// The language that best serves the given BCP 47 tag.
+///
+/// The tag is matched as by [`Language::from_tag`], save that malformed tags
+/// are stripped of their trailing subtags until they are well-formed.
+///
+/// [`Language::from_tag`]: ../enum.Language.html#method.from_tag
+pub fn resolve(tag : &str) -> Result<Language, tag::Error> {
+    let mut tag = tag.trim();
+    loop {
+        match Language::from_tag(tag) {
+            Err(tag::Error::Malformed(s)) => match tag.rfind(['-', '_']) {
+                Some(i) => tag = &tag[.. i],
+                None => return Err(tag::Error::Malformed(s)),
+            },
+            resolved => return resolved.map(|r| r.language),
+        }
+    }
+}
+
+
+/// Failure modes of dictionary retrieval.
+#[derive(Debug)]
+pub enum Error {
+    /// The tag designates no supported language.
+    Tag(tag::Error),
+    /// No provider has a dictionary for the language.
+    Unavailable(Language),
+    /// The dictionary for the language could not be loaded.
+    Load(Language, load::Error),
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match *self {
+            Error::Tag(ref e) => Some(e),
+            Error::Load(_, ref e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f : &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::Tag(ref e) => e.fmt(f),
+            Error::Unavailable(lang) => write!(f, "no dictionary available for `{}`", lang),
+            Error::Load(lang, ref e) => {
+                write!(f,
+                       "the dictionary for `{}` could not be loaded: {}",
+                       lang, e)
+            }
+        }
+    }
+}
+
+impl From<tag::Error> for Error {
+    fn from(err : tag::Error) -> Error { Error::Tag(err) }
+}
@@ -34,10 +34,46 @@ word boundaries, we find:
 By convention, even values inhibit hyphenation, whereas odd values mark
 valid breaks. Thus, having matched these patterns, the dictionary will
 offer "fir·kin" as a valid hyphenation.
+
+
+## Weighing breaks
+
+Beyond its parity, the value of a break hints at its strength: the higher
+the value, the later the level of patterns that established it, and the
+more specific the patterns. Breaks may also be given explicitly, by
+soft hyphens or dictionary exceptions, regardless of any pattern.
+[`Weigh::hyphenate_weighted`] pairs each break with such a [`Quality`], which
+a line breaker might use to favor better breaks:
+
+```ignore
+use hyphenation::score::{Quality, Weigh};
+
+let hyphenated = en_gb.hyphenate_weighted("firkin");
+let qualities : Vec<_> = hyphenated.breaks.iter().map(|b| b.quality).collect();
+assert_eq!(qualities, vec![Quality::Pattern(5)]);
+```
+
+Where typesetting conventions prefer some breaks over others, such as the
+boundaries of compound words in German or Dutch, patterns commonly set the
+preferred breaks apart with higher values, and [`Quality::rank`] divides
+breaks accordingly.
+
+
+[`Weigh::hyphenate_weighted`]: trait.Weigh.html#tymethod.hyphenate_weighted
+[`Quality`]: enum.Quality.html
+[`Quality::rank`]: enum.Quality.html#method.rank
 */
 
+use std::cmp::Reverse;
+use std::ops::Range;
+
 use hyphenation_commons::dictionary::extended::*;
 use hyphenation_commons::dictionary::*;
+use hyphenation_commons::HyphenationOptions;
+
+use crate::hyphenator::{is_grapheme_boundary, prepare, soft_hyphen_indices};
+use crate::hyphenator::{Break, Hyphenator, Word};
+use crate::text::Offset;
 
 
 /// Methods to evaluate each index in a string as an opportunity for
@@ -59,7 +95,7 @@ pub trait Score<'d> {
     fn denotes_opportunity(value : Self::Value) -> bool;
 }
 
-impl<'d> Score<'d> for Standard {
+impl<'d, D : AsRef<[u8]>> Score<'d> for Standard<D> {
     type Value = u8;
 
     #[inline]
@@ -85,7 +121,7 @@ impl<'d> Score<'d> for Standard {
     }
 }
 
-impl<'d> Score<'d> for Extended {
+impl<'d, D : AsRef<[u8]>> Score<'d> for Extended<D> {
     type Value = (u8, Option<&'d Subregion>);
 
     #[inline]
@@ -125,3 +161,270 @@ impl<'d> Score<'d> for Extended {
         values.into_iter().zip(regions).collect()
     }
 }
+
+
+/// The provenance and strength of a break. Qualities are ordered from worst
+/// to best: patterns by their value, then joints of compound words, then
+/// exceptions, then soft hyphens.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Quality {
+    /// A break found by patterns, with the odd value they assigned it.
+    Pattern(u8),
+    /// A break at the joint of a closed compound, found by its stems. See the
+    /// [`compound`](../compound/index.html) module.
+    Joint,
+    /// A break specified by the dictionary's exceptions.
+    Exception,
+    /// A break marked by a soft hyphen in the word itself.
+    SoftHyphen,
+}
+
+/// The class of a break, for typesetting conventions that distinguish
+/// preferred breaks, such as the boundaries of compound words, from
+/// secondary breaks between syllables.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Rank {
+    Secondary,
+    Preferred,
+}
+
+impl Quality {
+    /// The value assigned to the break by patterns, if it was found by them.
+    pub fn value(self) -> Option<u8> {
+        match self {
+            Quality::Pattern(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Classify the break as preferred if it was given explicitly, or if
+    /// patterns assigned it a value of at least `threshold`. The threshold
+    /// suited to a language depends on how its patterns were made.
+    pub fn rank(self, threshold : u8) -> Rank {
+        match self {
+            Quality::Pattern(v) if v < threshold => Rank::Secondary,
+            _ => Rank::Preferred,
+        }
+    }
+}
+
+
+/// A break paired with its quality.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Weighted<B> {
+    pub opportunity : B,
+    pub quality :     Quality,
+}
+
+impl<B : Break> Break for Weighted<B> {
+    #[inline]
+    fn index(&self) -> usize { self.opportunity.index() }
+
+    fn substitution(&self) -> Option<(Range<usize>, &str, &str)> {
+        self.opportunity.substitution()
+    }
+}
+
+impl<B : Offset> Offset for Weighted<B> {
+    #[inline]
+    fn offset(self, delta : usize) -> Self {
+        Weighted { opportunity : self.opportunity.offset(delta),
+                   quality :     self.quality, }
+    }
+}
+
+
+impl<'t, B> Word<'t, Weighted<B>> {
+    /// The word, with its breaks stripped of their quality.
+    pub fn unweighted(self) -> Word<'t, B> {
+        Word { text :   self.text,
+               breaks : self.breaks.into_iter().map(|b| b.opportunity).collect(), }
+    }
+}
+
+
+/// Hyphenation that weighs the breaks it finds.
+pub trait Weigh<'h>: Hyphenator<'h> {
+    /// Hyphenate a word as `hyphenate` does, pairing each break with its
+    /// quality.
+    fn hyphenate_weighted<'t>(&'h self, word : &'t str) -> Word<'t, Weighted<Self::Opportunity>> {
+        self.hyphenate_weighted_with(word, &self.options())
+    }
+
+    /// Hyphenate a word as `hyphenate_with` does, pairing each break with its
+    /// quality.
+    fn hyphenate_weighted_with<'t>(&'h self,
+                                   word : &'t str,
+                                   options : &HyphenationOptions)
+                                   -> Word<'t, Weighted<Self::Opportunity>>;
+}
+
+impl<'h, D : AsRef<[u8]>> Weigh<'h> for Standard<D> {
+    fn hyphenate_weighted_with<'t>(&'h self,
+                                   word : &'t str,
+                                   options : &HyphenationOptions)
+                                   -> Word<'t, Weighted<usize>> {
+        weigh(self, word, options)
+    }
+}
+
+impl<'h, D : AsRef<[u8]>> Weigh<'h> for Extended<D> {
+    fn hyphenate_weighted_with<'t>(&'h self,
+                                   word : &'t str,
+                                   options : &HyphenationOptions)
+                                   -> Word<'t, Weighted<Self::Opportunity>> {
+        weigh(self, word, options)
+    }
+}
+
+
+/// How a dictionary makes its opportunities from indices and values.
+pub(crate) trait Valued<'h>: Hyphenator<'h> + Score<'h> {
+    /// The opportunity at the given index, as marked by a soft hyphen.
+    fn plain(index : usize) -> Self::Opportunity;
+
+    /// The opportunity, moved to the given index.
+    fn moved(opportunity : Self::Opportunity, index : usize) -> Self::Opportunity;
+
+    /// The opportunity at the given index, and its value as assigned by
+    /// patterns.
+    fn valued(index : usize, value : Self::Value) -> (Self::Opportunity, u8);
+}
+
+impl<'h, D : AsRef<[u8]>> Valued<'h> for Standard<D> {
+    #[inline]
+    fn plain(index : usize) -> usize { index }
+
+    #[inline]
+    fn moved(_ : usize, index : usize) -> usize { index }
+
+    #[inline]
+    fn valued(index : usize, value : u8) -> (usize, u8) { (index, value) }
+}
+
+impl<'h, D : AsRef<[u8]>> Valued<'h> for Extended<D> {
+    #[inline]
+    fn plain(index : usize) -> Self::Opportunity { (index, None) }
+
+    #[inline]
+    fn moved((_, subregion) : Self::Opportunity, index : usize) -> Self::Opportunity {
+        (index, subregion)
+    }
+
+    #[inline]
+    fn valued(index : usize, (value, subregion) : Self::Value) -> (Self::Opportunity, u8) {
+        ((index, subregion), value)
+    }
+}
+
+/// Hyphenate a word within the limits of the given options, pairing each
+/// break with its quality. Should the word have more breaks than the options
+/// allow, the best are kept.
+pub(crate) fn weigh<'h, 't, H>(hyphenator : &'h H,
+                               word : &'t str,
+                               options : &HyphenationOptions)
+                               -> Word<'t, Weighted<H::Opportunity>>
+    where H : Valued<'h>,
+          H::Opportunity : Break,
+          H::Value : Copy
+{
+    let mut breaks : Vec<_> = match soft_hyphen_indices(word) {
+        Some(indices) => indices.into_iter()
+                                .map(|i| Weighted { opportunity : H::plain(i),
+                                                    quality :     Quality::SoftHyphen, })
+                                .collect(),
+        None => {
+            let prepared = prepare(word, hyphenator.normalization(), hyphenator.folding());
+            opportunities(hyphenator, &prepared.word, &options.unlimited())
+                .into_iter()
+                .filter_map(|Weighted { opportunity, quality }| {
+                    let i = prepared.realign(opportunity.index())?;
+                    Some(Weighted { opportunity : H::moved(opportunity, i),
+                                    quality })
+                })
+                .collect()
+        }
+    };
+    // Breaks that coincide once realigned with the original word are kept as
+    // one, of the best quality among them.
+    breaks.dedup_by(|b, kept| {
+              let coincide = b.index() == kept.index();
+              if coincide {
+                  kept.quality = std::cmp::max(kept.quality, b.quality);
+              }
+              coincide
+          });
+
+    if let Some(max) = options.max_breaks {
+        keep_best(&mut breaks, max, word.len());
+    }
+    Word { text : word,
+           breaks }
+}
+
+/// The opportunities found in a prepared word within the minima and minimum
+/// length of the given options, by exception or else by patterns, each with
+/// its quality.
+fn opportunities<'h, H>(hyphenator : &'h H,
+                        word : &str,
+                        options : &HyphenationOptions)
+                        -> Vec<Weighted<H::Opportunity>>
+    where H : Valued<'h>,
+          H::Value : Copy
+{
+    let bounds = match hyphenator.boundaries_with(word, options) {
+        Some(bounds) => bounds,
+        None => return vec![],
+    };
+    match hyphenator.exception_within(word, bounds) {
+        Some(known) => known.into_iter()
+                            .map(|opportunity| Weighted { opportunity,
+                                                          quality : Quality::Exception })
+                            .collect(),
+        None => patterns(hyphenator, word, bounds),
+    }
+}
+
+/// The opportunities that arise by pattern between the specified byte indices
+/// of a prepared word, weighed by the values that patterns assigned them.
+pub(crate) fn patterns<'h, H>(hyphenator : &'h H,
+                              word : &str,
+                              (l, r) : (usize, usize))
+                              -> Vec<Weighted<H::Opportunity>>
+    where H : Valued<'h>,
+          H::Value : Copy
+{
+    (1 .. word.len()).zip(hyphenator.score(word))
+                     .filter(|&(i, v)| {
+                         let valid = H::denotes_opportunity(v);
+                         let within_bounds = i >= l && i <= r;
+                         let legal_index = is_grapheme_boundary(word, i);
+                         valid && within_bounds && legal_index
+                     })
+                     .map(|(i, v)| {
+                         let (opportunity, value) = H::valued(i, v);
+                         Weighted { opportunity,
+                                    quality : Quality::Pattern(value) }
+                     })
+                     .collect()
+}
+
+/// Keep no more than `max` breaks, in their order: those of the highest
+/// quality, and, among equals, those nearest the middle of the word.
+pub(crate) fn keep_best<B : Break>(breaks : &mut Vec<Weighted<B>>, max : usize, length : usize) {
+    if breaks.len() <= max {
+        return;
+    }
+
+    let mut ranked : Vec<usize> = (0 .. breaks.len()).collect();
+    ranked.sort_by_key(|&k| {
+              let b = &breaks[k];
+              (Reverse(b.quality), (2 * b.index()).abs_diff(length))
+          });
+    let mut kept = vec![false; breaks.len()];
+    for &k in &ranked[.. max] {
+        kept[k] = true;
+    }
+    let mut kept = kept.into_iter();
+    breaks.retain(|_| kept.next() == Some(true));
+}
@@ -0,0 +1,119 @@
+/*! Hyphenation of running text
+
+Hyphenators operate on individual words, leaving segmentation to the caller.
+This module provides the missing glue: text is split according to the word
+boundaries of [Unicode Standard Annex #29], and each word is hyphenated on
+its own, while whitespace, punctuation and numbers are left untouched.
+
+```ignore
+use hyphenation::{Hyphenator, Iter};
+
+let text = "ever-burning sulphur unconsumed";
+let hyphenated = en_us.hyphenate_text(text);
+assert_eq!(hyphenated.breaks, &[9, 16, 23, 26]);
+
+let marked : String = hyphenated.iter().collect();
+assert_eq!(marked, "ever-burn-ing sul-phur un-con-sumed");
+```
+
+The result is a [`Word`] spanning the whole text, whose breaks are byte
+indices into the original string; as such, it can be iterated over just like
+any single hyphenated word.
+
+
+## Punctuation
+
+Hyphen-joined compounds are split into their constituents, each of which is
+hyphenated individually. Words containing an apostrophe – such as elisions,
+or contractions – are likewise hyphenated on either side of the apostrophe,
+so that no dictionary is asked to make sense of the punctuation itself.
+
+
+[Unicode Standard Annex #29]: https://www.unicode.org/reports/tr29/
+[`Word`]: ../hyphenator/struct.Word.html
+*/
+
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::extended::Subregion;
+use crate::hyphenator::{Hyphenator, Word};
+
+
+/// Opportunities that can be moved from a word into the text that contains it.
+pub trait Offset {
+    /// The same opportunity, its index advanced by `delta` bytes.
+    fn offset(self, delta : usize) -> Self;
+}
+
+impl Offset for usize {
+    #[inline]
+    fn offset(self, delta : usize) -> Self { self + delta }
+}
+
+impl Offset for (usize, Option<&Subregion>) {
+    #[inline]
+    fn offset(self, delta : usize) -> Self { (self.0 + delta, self.1) }
+}
+
+
+/// A hyphenator for running text, which segments its input into words and
+/// hyphenates each word with the underlying dictionary.
+#[derive(Clone, Copy, Debug)]
+pub struct TextHyphenator<'h, H> {
+    hyphenator : &'h H,
+}
+
+impl<'h, H> TextHyphenator<'h, H>
+    where H : Hyphenator<'h>,
+          H::Opportunity : Offset
+{
+    /// Prepare the given dictionary for the hyphenation of running text.
+    pub fn new(hyphenator : &'h H) -> Self { TextHyphenator { hyphenator } }
+
+    /// The hyphenable words in the text, paired with their byte index.
+    ///
+    /// Whitespace, punctuation and numbers are skipped, and words containing
+    /// apostrophes are further split into their constituents.
+    pub fn words<'t>(&self, text : &'t str) -> impl Iterator<Item = (usize, &'t str)> + 't {
+        text.split_word_bound_indices()
+            .flat_map(|(i, word)| elisions(word).map(move |(j, part)| (i + j, part)))
+            .filter(|&(_, word)| is_hyphenable(word))
+    }
+
+    /// Hyphenate every word in the text. The resulting breaks are byte
+    /// indices into the original text.
+    pub fn hyphenate<'t>(&self, text : &'t str) -> Word<'t, H::Opportunity> {
+        let breaks = self.words(text)
+                         .flat_map(|(i, word)| {
+                             self.hyphenator
+                                 .hyphenate(word)
+                                 .breaks
+                                 .into_iter()
+                                 .map(move |o| o.offset(i))
+                         })
+                         .collect();
+
+        Word { text, breaks }
+    }
+}
+
+
+/// Whether the segment is a word that may be submitted for hyphenation.
+fn is_hyphenable(segment : &str) -> bool {
+    segment.chars().any(char::is_alphabetic) && !segment.chars().any(char::is_numeric)
+}
+
+/// The parts of a word on either side of its apostrophes, if any.
+fn elisions(word : &str) -> impl Iterator<Item = (usize, &str)> {
+    let mut start = 0;
+    word.split(is_apostrophe).map(move |part| {
+                                 let i = start;
+                                 start += part.len();
+                                 // Skip over the apostrophe that ended this part.
+                                 start += word[start ..].chars().next().map_or(0, char::len_utf8);
+                                 (i, part)
+                             })
+}
+
+#[inline]
+fn is_apostrophe(c : char) -> bool { c == '\'' || c == '\u{2019}' }
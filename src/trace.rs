@@ -0,0 +1,276 @@
+/*! Tracing the patterns that score a word
+
+When a dictionary hyphenates a word unexpectedly, the fault lies with some
+pattern, or with the absence of one. [`Explain::explain`] retraces the
+scoring of a word, recording every pattern that matched it, the values each
+pattern assigned, and which of them prevailed at each index:
+
+```ignore
+use hyphenation::trace::Explain;
+
+let trace = en_gb.explain("firkin");
+assert_eq!(trace.values, vec![2, 2, 5, 2, 0]);
+
+let prevailing = trace.winner(3).unwrap();
+assert_eq!(prevailing.pattern(), "r5kin.");
+```
+
+A trace is displayed as a table of the matched patterns, aligned on the
+word, above the final score:
+
+```text
+«f|i|r|k|i|n»
+-------------
+«f i2
+  2i r
+     r2k
+     r5k i n»
+       k1i n
+       k2i n»
+-------------
+ f|i|r|k|i|n
+  2 2 5 2 0
+```
+
+Traces only account for patterns. Exceptions take precedence over them, and
+minima are applied to the score afterwards.
+
+
+[`Explain::explain`]: trait.Explain.html#tymethod.explain
+*/
+
+use std::fmt;
+
+use hyphenation_commons::dictionary::extended::*;
+use hyphenation_commons::dictionary::*;
+use hyphenation_commons::{Folding, Normalization};
+
+use crate::hyphenator::prepare;
+use crate::score::Score;
+
+
+/// A pattern matched within a word.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Match<'d> {
+    /// The byte index at which the pattern matched, within the word enclosed
+    /// by the `.` delimiters of word boundaries.
+    pub start :     usize,
+    /// The letters of the pattern, including any delimiter.
+    pub letters :   String,
+    /// The values that the pattern assigns, at byte indices into its letters.
+    pub loci :      &'d [Locus],
+    /// The alteration carried by an extended pattern, with the value that it
+    /// assigns.
+    pub subregion : Option<&'d (Locus, Subregion)>,
+}
+
+impl<'d> Match<'d> {
+    /// The pattern in the notation of pattern files, as in `r5kin`. The
+    /// alterations of extended patterns are left out.
+    pub fn pattern(&self) -> String {
+        let mut pattern = String::with_capacity(self.letters.len() + self.loci.len());
+        for (i, c) in self.letters.char_indices() {
+            for l in self.all_loci().filter(|l| l.index as usize == i) {
+                pattern.push_str(&l.value.to_string());
+            }
+            pattern.push(c);
+        }
+        for l in self.all_loci()
+                     .filter(|l| l.index as usize == self.letters.len())
+        {
+            pattern.push_str(&l.value.to_string());
+        }
+
+        pattern
+    }
+
+    /// The values that the pattern assigns, paired with the byte index into
+    /// the word of the break they concern. Values assigned beyond the word
+    /// boundaries are left out.
+    pub fn values(&self, word : &str) -> Vec<(usize, u8)> {
+        self.all_loci()
+            .map(|l| (self.start + l.index as usize, l.value))
+            .filter(|&(k, _)| k > 1 && k <= word.len())
+            .map(|(k, v)| (k - 1, v))
+            .collect()
+    }
+
+    /// The loci of the pattern, and that of its subregion unless a standard
+    /// locus shares its index.
+    fn all_loci(&self) -> impl Iterator<Item = &'d Locus> {
+        let loci = self.loci;
+        let subregion = self.subregion
+                            .map(|(l, _)| l)
+                            .filter(|s| loci.iter().all(|l| l.index != s.index));
+        loci.iter().chain(subregion)
+    }
+}
+
+
+/// The record of a word's scoring.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Trace<'d> {
+    /// The word, normalized and case-folded as for hyphenation.
+    pub word :    String,
+    /// The patterns matched within the word, in order of their start.
+    pub matches : Vec<Match<'d>>,
+    /// The score of the word, as given by [`Score::score`]: the prevailing
+    /// value for each byte index, from the first break onwards.
+    ///
+    /// [`Score::score`]: ../score/trait.Score.html#tymethod.score
+    pub values :  Vec<u8>,
+    /// For each value of the score, the index in `matches` of the pattern
+    /// which assigned it, if any.
+    pub winners : Vec<Option<usize>>,
+}
+
+impl<'d> Trace<'d> {
+    /// The pattern which prevailed at the given byte index of the word.
+    pub fn winner(&self, index : usize) -> Option<&Match<'d>> {
+        index.checked_sub(1)
+             .and_then(|i| self.winners.get(i).copied().flatten())
+             .map(|m| &self.matches[m])
+    }
+}
+
+
+/// Dictionaries whose scoring can be traced.
+pub trait Explain<'d>: Score<'d> {
+    /// Score the word as hyphenation would, after normalizing and lowercasing
+    /// it, recording each pattern that matched.
+    fn explain(&'d self, word : &str) -> Trace<'d>;
+}
+
+impl<'d, D : AsRef<[u8]>> Explain<'d> for Standard<D> {
+    fn explain(&'d self, word : &str) -> Trace<'d> {
+        trace(word, self.normalization(), self.language().folding(), |query| {
+            let mut tallies = self.prefix_tallies(query);
+            let mut matches = vec![];
+            while let Some(tally) = tallies.next() {
+                matches.push((tallies.matched_length(), &tally[..], None));
+            }
+            matches
+        })
+    }
+}
+
+impl<'d, D : AsRef<[u8]>> Explain<'d> for Extended<D> {
+    fn explain(&'d self, word : &str) -> Trace<'d> {
+        trace(word, self.normalization(), self.language().folding(), |query| {
+            let mut tallies = self.prefix_tallies(query);
+            let mut matches = vec![];
+            while let Some(tally) = tallies.next() {
+                matches.push((tallies.matched_length(),
+                              &tally.standard[..],
+                              tally.subregion.as_ref()));
+            }
+            matches
+        })
+    }
+}
+
+type Prefix<'d> = (usize, &'d [Locus], Option<&'d (Locus, Subregion)>);
+
+fn trace<'d, F>(word : &str, form : Normalization, folding : Folding, prefixes : F) -> Trace<'d>
+    where F : Fn(&[u8]) -> Vec<Prefix<'d>>
+{
+    let word = prepare(word, form, folding).word;
+    let match_str = [".", &word, "."].concat();
+    let hyphenable_length = word.len();
+    let mut values : Vec<u8> = vec![0; hyphenable_length.saturating_sub(1)];
+    let mut winners : Vec<Option<usize>> = vec![None; values.len()];
+    let mut matches = vec![];
+
+    for i in 0 .. match_str.len() - 1 {
+        for (length, loci, subregion) in prefixes(&match_str.as_bytes()[i ..]) {
+            let m = matches.len();
+            // As in extended scoring, the subregion comes first, and standard
+            // values equal to its own do not replace it.
+            for &Locus { index, value } in subregion.map(|(l, _)| l).into_iter().chain(loci) {
+                let k = i + index as usize;
+                if k > 1 && k <= hyphenable_length && value > values[k - 2] {
+                    values[k - 2] = value;
+                    winners[k - 2] = Some(m);
+                }
+            }
+            matches.push(Match { start : i,
+                                 letters : match_str[i .. i + length].to_string(),
+                                 loci,
+                                 subregion });
+        }
+    }
+
+    Trace { word : word.into_owned(),
+            matches,
+            values,
+            winners }
+}
+
+
+/// Values above 9, which pattern files cannot express, are shown as `+`.
+impl fmt::Display for Trace<'_> {
+    fn fmt(&self, f : &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Letters take odd columns, and the breaks between them even columns,
+        // where the delimiters of word boundaries are shown too.
+        let length = self.word.len();
+        let width = 2 * self.word.chars().count() + 1;
+        let mut chars_before = vec![0; length + 2];
+        for (j, (b, c)) in self.word.char_indices().enumerate() {
+            for n in &mut chars_before[b + 1 ..= b + c.len_utf8()] {
+                *n = j;
+            }
+        }
+        chars_before[length + 1] = width / 2;
+        let letter_column = |p : usize| match p {
+            0 => 0,
+            p if p == length + 1 => width - 1,
+            p => 2 * chars_before[p] + 1,
+        };
+        let digit = |v : u8| std::char::from_digit(u32::from(v), 10).unwrap_or('+');
+
+        let mut lines = vec![];
+        let mut header = vec![' '; width];
+        let mut footer = vec![' '; width];
+        let mut score = vec![' '; width];
+        header[0] = '«';
+        header[width - 1] = '»';
+        for (b, c) in self.word.char_indices() {
+            header[letter_column(b + 1)] = c;
+            footer[letter_column(b + 1)] = c;
+            if b > 0 {
+                header[letter_column(b + 1) - 1] = '|';
+                footer[letter_column(b + 1) - 1] = '|';
+                score[letter_column(b + 1) - 1] = digit(self.values[b - 1]);
+            }
+        }
+        lines.push(header);
+        lines.push(vec!['-'; width]);
+        for m in &self.matches {
+            let mut line = vec![' '; width];
+            for (k, v) in m.values(&self.word) {
+                line[letter_column(k + 1) - 1] = digit(v);
+            }
+            for (o, c) in m.letters.char_indices() {
+                let p = m.start + o;
+                line[letter_column(p)] = match p {
+                    0 => '«',
+                    p if p == length + 1 => '»',
+                    _ => c,
+                };
+            }
+            lines.push(line);
+        }
+        lines.push(vec!['-'; width]);
+        lines.push(footer);
+        lines.push(score);
+
+        for (i, line) in lines.iter().enumerate() {
+            if i > 0 {
+                f.write_str("\n")?;
+            }
+            let line : String = line.iter().collect();
+            f.write_str(line.trim_end())?;
+        }
+        Ok(())
+    }
+}
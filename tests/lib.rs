@@ -8,6 +8,7 @@ use std::path::Path;
 
 use once_cell::sync::Lazy;
 use quickcheck::{quickcheck, TestResult};
+use unicode_segmentation::UnicodeSegmentation;
 
 extern crate hyphenation;
 extern crate hyphenation_commons;
@@ -46,15 +47,15 @@ fn collected_equals_original() {
 #[test]
 fn opportunities_within_bounds() {
     fn property(s : String) -> TestResult {
-        let ci : Vec<_> = s.char_indices().collect();
+        let gi : Vec<_> = s.grapheme_indices(true).collect();
         let (l_min, r_min) = EnglishUS.minima();
-        let s_len = ci.len();
+        let s_len = gi.len();
         if s_len < l_min + r_min {
             return TestResult::discard();
         }
 
         let os : Vec<_> = EN_US.opportunities(&s);
-        let ((l, _), (r, _)) = (ci[l_min], ci[s_len - r_min]);
+        let ((l, _), (r, _)) = (gi[l_min], gi[s_len - r_min]);
         let within_bounds = |&i| i >= l && i <= r;
 
         TestResult::from_bool(os.iter().all(within_bounds))
@@ -123,10 +124,56 @@ fn basics_extended() {
     assert_eq!(v1, vec!["esz", "szé"]);
 }
 
+#[test]
+fn consecutive_substitutions() {
+    // "nny" breaks as "ny-ny", carrying "ny" over to the next segment, which
+    // itself ends at the "ssz" break.
+    let w0 = "könnyesszemű";
+    let v0 : Vec<_> = HU.hyphenate(w0).into_iter().segments().collect();
+    assert_eq!(v0, vec!["köny", "nyesz", "sze", "mű"]);
+
+    let collected : String = HU.hyphenate(w0).into_iter().collect();
+    assert_eq!(collected, "köny-nyesz-sze-mű");
+}
+
+#[test]
+fn borrowed_extended_iteration() {
+    let h_w0 = HU.hyphenate("asszonnyal");
+    let h_w1 = HU.hyphenate("esszé");
+
+    let seg0 = h_w0.iter().segments();
+    let seg1 = h_w1.iter().segments();
+    assert_eq!(seg0.size_hint(), (3, Some(3)));
+    assert_eq!(seg1.size_hint(), (2, Some(2)));
+
+    // Borrowed words can be iterated over repeatedly, and inspected after.
+    let v0 : Vec<_> = seg0.clone().collect();
+    let v1 : Vec<_> = seg1.collect();
+    assert_eq!(v0, vec!["asz", "szony", "nyal"]);
+    assert_eq!(v0, seg0.collect::<Vec<_>>());
+    assert_eq!(v1, vec!["esz", "szé"]);
+    assert_eq!(h_w0.breaks.len(), 2);
+
+    let marked : Vec<_> = h_w0.iter().collect();
+    assert_eq!(marked, vec!["asz-", "szony-", "nyal"]);
+    let marked : String = h_w0.segments_with_mark("·").collect();
+    assert_eq!(marked, "asz·szony·nyal");
+
+    let mut seg1 = h_w1.iter().segments();
+    seg1.next();
+    assert_eq!(seg1.size_hint(), (1, Some(1)));
+    seg1.next();
+    assert_eq!(seg1.size_hint(), (0, Some(0)));
+
+    // The same holds of standard words.
+    let marked : String = EN_US.hyphenate("anfractuous").segments_with_mark("·").collect();
+    assert_eq!(marked, "an·frac·tu·ous");
+}
+
 #[test]
 fn special_casing() {
     let w0 = "İbrahim";
-    let v0 : Vec<_> = TR.hyphenate(&w0).into_iter().segments().collect();
+    let v0 : Vec<_> = TR.hyphenate(w0).into_iter().segments().collect();
     assert_eq!(v0, vec!["İb", "ra", "him"]);
 
     let w1 = "İLGİNÇ";
@@ -146,6 +193,172 @@ fn special_casing() {
     assert_eq!(v3, vec!["MU", "CİLAGİ", "NOUS"]);
 }
 
+#[test]
+fn case_folding() {
+    fn segments<'h, H>(h : &'h H, word : &str) -> Vec<String>
+        where H : Hyphenator<'h, Opportunity = usize>
+    {
+        h.hyphenate(word)
+         .into_iter()
+         .segments()
+         .map(str::to_lowercase)
+         .collect()
+    }
+
+    // Words fold to their lowercase forms, save those whose folding changes
+    // their length, which are realigned.
+    let de = fiat_std(German1996);
+    assert_eq!(segments(&de, "STRASSE"), segments(&de, "strasse"));
+    assert_eq!(segments(&de, "STRAẞE"), vec!["stra", "ße"]);
+    assert_eq!(segments(&de, "STRAẞE"), segments(&de, "Straße"));
+    let el = fiat_std(GreekMono);
+    assert_eq!(segments(&el, "ΑΝΘΡΩΠΟΣ"), segments(&el, "ανθρωπος"));
+
+    // Turkish folds "I" to dotless "ı", and Lithuanian retains the dot of "i"
+    // under accents.
+    let kirmizi : Vec<_> = TR.hyphenate("KIRMIZI").into_iter().segments().collect();
+    assert_eq!(kirmizi, vec!["KIR", "MI", "ZI"]);
+    let kirmizi : Vec<_> = TR.hyphenate("kırmızı").into_iter().segments().collect();
+    assert_eq!(kirmizi, vec!["kır", "mı", "zı"]);
+    let lt = fiat_std(Lithuanian);
+    let kaimynas : Vec<_> = lt.hyphenate("KAÌMYNAS").into_iter().segments().collect();
+    assert_eq!(kaimynas, vec!["KAÌMY", "NAS"]);
+    let kaimynas : Vec<_> = lt.hyphenate("kai\u{307}\u{300}mynas").into_iter().segments().collect();
+    assert_eq!(kaimynas, vec!["kai\u{307}\u{300}my", "nas"]);
+
+    // Patterns are folded likewise, and merged should they coincide.
+    let ss = DictionaryBuilder::new(German1996).patterns(vec!["1ẞ", "s3s"])
+                                               .minima(1, 1)
+                                               .standard()
+                                               .unwrap();
+    assert_eq!(ss.hyphenate("STRASSE").breaks, vec![4, 5]);
+    assert_eq!(ss.hyphenate("Straße").breaks, vec![4]);
+
+    // Extended patterns keep their subregion when folding alters their length.
+    let ligature = DictionaryBuilder::new(Hungarian).patterns(vec!["ﬂös5s1ze/sz=,3,1"])
+                                                    .minima(1, 1)
+                                                    .extended()
+                                                    .unwrap();
+    let plain = DictionaryBuilder::new(Hungarian).patterns(vec!["flös5s1ze/sz=,4,1"])
+                                                 .minima(1, 1)
+                                                 .extended()
+                                                 .unwrap();
+    assert_eq!(ligature.hyphenate("FLÖSSZE").breaks,
+               plain.hyphenate("flössze").breaks);
+}
+
+#[test]
+fn grapheme_boundaries() {
+    fn property(s : String) -> bool {
+        let bounds : Vec<_> = s.grapheme_indices(true).map(|(i, _)| i).collect();
+        EN_US.hyphenate(&s).breaks.iter().all(|i| bounds.contains(i))
+    }
+
+    quickcheck(property as fn(String) -> bool);
+
+    // Breaks set by patterns within a grapheme are discarded: neither
+    // combining marks nor Indic vowel signs and viramas are severed from
+    // their letter.
+    let within = DictionaryBuilder::new(Hindi).patterns(vec!["e1\u{301}", "1s", "न1\u{94d}", "1द"])
+                                              .minima(1, 1)
+                                              .standard()
+                                              .unwrap();
+    assert_eq!(within.hyphenate("cafe\u{301}s").breaks, vec![6]);
+    let hindi : Vec<_> = within.hyphenate("हिन्दी").into_iter().segments().collect();
+    assert_eq!(hindi, vec!["हिन्दी"]);
+
+    // Minima are counted in graphemes.
+    let minima = DictionaryBuilder::new(EnglishUS).patterns(vec!["1b"])
+                                                  .minima(2, 1)
+                                                  .standard()
+                                                  .unwrap();
+    assert_eq!(minima.hyphenate("a\u{301}bb").breaks, vec![4]);
+    let th = fiat_std(Thai);
+    let v : Vec<_> = th.hyphenate("มหาวิทยาลัย").into_iter().segments().collect();
+    assert_eq!(v, vec!["มหา", "วิท", "ยาลัย"]);
+}
+
+#[test]
+fn hyphenation_options() {
+    use hyphenation::score::{Quality, Weigh};
+
+    assert_eq!(EN_US.options, HyphenationOptions::from(EnglishUS));
+    assert_eq!(EN_US.hyphenate("incomprehensibilities").breaks,
+               vec![2, 5, 8, 11, 13, 16, 17]);
+
+    // Per call, minima also bound exceptions, and short words are spared.
+    let wide = EN_US.options.minima(3, 3);
+    assert_eq!(EN_US.hyphenate_with("anfractuous", &wide).breaks, vec![6, 8]);
+    assert_eq!(EN_US.hyphenate_with("hyphenation", &wide).breaks, vec![6, 7]);
+    let long = EN_US.options.min_length(12);
+    assert!(EN_US.hyphenate_with("anfractuous", &long).breaks.is_empty());
+    assert_eq!(EN_US.hyphenate_with("incomprehensibilities", &long).breaks.len(), 7);
+
+    // Words as long as the minima, or the minimum length, may yet be broken.
+    let b = DictionaryBuilder::new(EnglishUS).patterns(vec!["1b"])
+                                             .minima(2, 2)
+                                             .standard()
+                                             .unwrap();
+    assert_eq!(b.hyphenate("aabb").breaks, vec![2]);
+    assert!(b.hyphenate("abb").breaks.is_empty());
+    assert_eq!(b.hyphenate_with("aabb", &b.options.min_length(4)).breaks, vec![2]);
+    assert!(b.hyphenate_with("aabb", &b.options.min_length(5)).breaks.is_empty());
+
+    // The best breaks are kept, then those nearest the middle of the word.
+    let single = EN_US.options.max_breaks(1);
+    assert_eq!(EN_US.hyphenate_with("anfractuous", &single).breaks, vec![2]);
+    assert_eq!(EN_US.hyphenate_with("incomprehensibilities", &single).breaks, vec![11]);
+    let double = EN_US.options.max_breaks(2);
+    assert_eq!(EN_US.hyphenate_with("incomprehensibilities", &double).breaks, vec![8, 11]);
+    assert_eq!(EN_US.hyphenate_with("hy\u{ad}phen\u{ad}ation", &single).breaks, vec![8]);
+    let weighted = EN_US.hyphenate_weighted_with("anfractuous", &single);
+    let qualities : Vec<_> = weighted.breaks.iter().map(|b| b.quality).collect();
+    assert_eq!(qualities, vec![Quality::Pattern(3)]);
+    assert!(HU.hyphenate_with("asszonnyal", &HU.options.max_breaks(0)).breaks.is_empty());
+
+    // Per dictionary, options apply to every word, and survive storage.
+    let mut headlines = EN_US.clone();
+    headlines.options = headlines.options.min_length(8).max_breaks(1);
+    assert!(headlines.hyphenate("hyphen").breaks.is_empty());
+    assert_eq!(headlines.hyphenate("incomprehensibilities").breaks, vec![11]);
+    let mut buffer = vec![];
+    headlines.to_writer(&mut buffer).unwrap();
+    let loaded = Standard::from_reader(EnglishUS, &mut buffer.as_slice()).unwrap();
+    assert_eq!(loaded.options, headlines.options);
+}
+
+#[test]
+fn normalization() {
+    // The bundled dictionaries are in NFC, to which words in NFD are
+    // normalized; breaks are realigned with the word as given.
+    let fr = fiat_std(French);
+    assert_eq!(fr.normalization(), Normalization::Nfc);
+    assert_eq!(fr.hyphenate("décédé").breaks, vec![3]);
+    let nfd = "de\u{301}ce\u{301}de\u{301}";
+    assert_eq!(fr.hyphenate(nfd).breaks, vec![4]);
+    let v : Vec<_> = fr.hyphenate(nfd).into_iter().segments().collect();
+    assert_eq!(v, vec!["de\u{301}", "ce\u{301}de\u{301}"]);
+    let v : Vec<_> = fr.hyphenate("RE\u{301}E\u{301}LU").into_iter().segments().collect();
+    assert_eq!(v, vec!["RE\u{301}", "E\u{301}LU"]);
+
+    // Compatibility forms may expand a single character into several; breaks
+    // within it are moved before it.
+    let nfkc = DictionaryBuilder::new(EnglishUS).patterns(vec!["1ce", "f1fi"])
+                                                .normalization(Normalization::Nfkc)
+                                                .minima(1, 1)
+                                                .standard()
+                                                .unwrap();
+    assert_eq!(nfkc.normalization(), Normalization::Nfkc);
+    assert_eq!(nfkc.hyphenate("office").breaks, vec![2, 4]);
+    assert_eq!(nfkc.hyphenate("o\u{fb03}ce").breaks, vec![1, 4]);
+
+    // The form survives storage.
+    let mut buffer = vec![];
+    nfkc.to_writer(&mut buffer).unwrap();
+    let loaded = Standard::from_reader(EnglishUS, &mut buffer.as_slice()).unwrap();
+    assert_eq!(loaded.normalization(), Normalization::Nfkc);
+}
+
 #[test]
 fn language_mismatch_on_load() {
     let file = File::open("./dictionaries/mul-ethi.standard.bincode").unwrap();
@@ -154,9 +367,481 @@ fn language_mismatch_on_load() {
 }
 
 #[test]
-fn text() {
-    use unicode_segmentation::UnicodeSegmentation;
+fn borrowed_dictionaries() {
+    let bytes = std::fs::read("dictionaries/en-us.standard.bincode").unwrap();
+    let en_us : Standard<&[u8]> = Standard::from_bytes(EnglishUS, &bytes).unwrap();
+    for w in &["anfractuous", "hyphenation", "ribonuclease", "MUCİLAGİNOUS"] {
+        assert_eq!(en_us.hyphenate(w).breaks, EN_US.hyphenate(w).breaks);
+    }
+    assert!(Standard::from_bytes(EnglishGB, &bytes).is_err());
+
+    let bytes = std::fs::read("dictionaries/hu.extended.bincode").unwrap();
+    let hu : Extended<&[u8]> = Extended::from_bytes(Hungarian, &bytes).unwrap();
+    let v : Vec<_> = hu.hyphenate("asszonnyal").into_iter().segments().collect();
+    assert_eq!(v, vec!["asz", "szony", "nyal"]);
+
+    // Borrowed dictionaries can be stored like any other.
+    let mut stored = vec![];
+    en_us.to_writer(&mut stored).unwrap();
+    let reloaded = Standard::from_reader(EnglishUS, &mut stored.as_slice()).unwrap();
+    assert_eq!(reloaded.hyphenate("hyphenation").breaks, vec![2, 6, 7]);
+}
+
+#[test]
+fn dictionary_headers() {
+    use hyphenation::load::{Error, Header, Kind};
+    use hyphenation_commons::header;
+
+    let bytes = std::fs::read("dictionaries/en-us.standard.bincode").unwrap();
+    let (header, _) = Header::read(&bytes).unwrap();
+    assert_eq!((header.kind, header.language), (Kind::Standard, EnglishUS));
+    assert_eq!(header.source, "CTAN 2021-03-22");
+
+    // Long sources are cut short on a character boundary, and read back whole.
+    let long = Header::new(Kind::Standard, EnglishUS, &"é".repeat(200));
+    assert_eq!(long.source, "é".repeat(127));
+    let mut written = vec![];
+    long.write(&mut written, b"body").unwrap();
+    assert_eq!(Header::read(&written).unwrap(), (long, &b"body"[..]));
+
+    let load = |bytes : &[u8]| Standard::from_bytes(EnglishUS, bytes).map(|_| ());
+    match load(&bytes[.. bytes.len() - 1]) {
+        Err(Error::Corrupted(header::Error::Truncated)) => (),
+        r => panic!("expected a truncated dictionary, got {:?}", r),
+    }
+
+    let mut flipped = bytes.clone();
+    *flipped.last_mut().unwrap() ^= 1;
+    match load(&flipped) {
+        Err(Error::Corrupted(header::Error::Checksum { .. })) => (),
+        r => panic!("expected a checksum mismatch, got {:?}", r),
+    }
+
+    let mut versioned = bytes.clone();
+    versioned[8] += 1;
+    match load(&versioned) {
+        Err(Error::VersionMismatch { found, supported }) => assert_eq!(found, supported + 1),
+        r => panic!("expected a version mismatch, got {:?}", r),
+    }
+
+    match load(&bytes[8 ..]) {
+        Err(Error::Corrupted(header::Error::Magic)) => (),
+        r => panic!("expected a missing header, got {:?}", r),
+    }
+
+    let bytes = std::fs::read("dictionaries/hu.extended.bincode").unwrap();
+    match Standard::from_reader(Hungarian, &mut bytes.as_slice()) {
+        Err(Error::KindMismatch { expected : Kind::Standard,
+                                  found : Kind::Extended, }) => (),
+        r => panic!("expected a kind mismatch, got {:?}", r.map(|_| ())),
+    }
+}
+
+#[test]
+fn command_line() {
+    use std::io::Write;
+    use std::process::{Command, Stdio};
+
+    let hyphenate = |args : &[&str], input : &str| -> (bool, String) {
+        let mut child = Command::new(env!("CARGO_BIN_EXE_hyphenate"))
+            .args(["--dict", "dictionaries/en-us.standard.bincode"].iter().chain(args))
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .unwrap();
+        // The child may exit on error before reading its input.
+        let _ = child.stdin.take().unwrap().write_all(input.as_bytes());
+        let output = child.wait_with_output().unwrap();
+        (output.status.success(), String::from_utf8(output.stdout).unwrap())
+    };
+
+    let text = "ever-burning sulphur\nunconsumed\n";
+    assert_eq!(hyphenate(&["--lang", "en-US"], text),
+               (true, "ever-burn-ing sul-phur\nun-con-sumed\n".to_owned()));
+    assert_eq!(hyphenate(&["--output", "soft"], "sulphur"),
+               (true, "sul\u{ad}phur\n".to_owned()));
+    assert_eq!(hyphenate(&["-o", "words", "--mark=·"], text),
+               (true, "ever\nburn·ing\nsul·phur\nun·con·sumed\n".to_owned()));
+    assert_eq!(hyphenate(&[], "evenly").1, "evenly\n");
+    assert_eq!(hyphenate(&["--minima", "1,1"], "evenly").1, "even-ly\n");
+
+    let exceptions = std::env::temp_dir().join("hyphenate-exceptions.txt");
+    std::fs::write(&exceptions, "% exceptions\nsulp-hur\n").unwrap();
+    assert_eq!(hyphenate(&["-e", exceptions.to_str().unwrap()], "sulphur").1,
+               "sulp-hur\n");
+
+    assert!(!hyphenate(&["--lang", "en-gb"], text).0);
+    assert!(!hyphenate(&["--output", "bold"], text).0);
+}
+
+#[test]
+fn html() {
+    use hyphenation::html::{Error, HtmlHyphenator, SoftHyphen};
+
+    let de = fiat_std(German1996);
+    let html = HtmlHyphenator::new(EnglishUS, &*EN_US).dictionary(German1996, &de)
+                                                      .soft_hyphen(SoftHyphen::Entity);
+
+    let document = r#"<!DOCTYPE html>
+<p title="hyphenation" class=anfractuous>Hyphenation &amp; anfractuous<br>
+<span LANG="de-1996-DE">Silbentrennung</span> <q lang="fr">hyphénation</q>
+<!-- hyphenation --><code>hyphenation <b>hyphenation</b></code></p>
+<script>if (a<b) { hyphenation(); }</script><style>p > b { }</style>"#;
+    let expected = r#"<!DOCTYPE html>
+<p title="hyphenation" class=anfractuous>Hy&shy;phen&shy;a&shy;tion &amp; an&shy;frac&shy;tu&shy;ous<br>
+<span LANG="de-1996-DE">Sil&shy;ben&shy;tren&shy;nung</span> <q lang="fr">hyphénation</q>
+<!-- hyphenation --><code>hyphenation <b>hyphenation</b></code></p>
+<script>if (a<b) { hyphenation(); }</script><style>p > b { }</style>"#;
+    assert_eq!(html.hyphenate(document).unwrap(), expected);
+
+    // Soft hyphens are not duplicated, and stray markup is tolerated.
+    let html = html.soft_hyphen(SoftHyphen::Character);
+    let hyphenated = html.hyphenate("<p>anfractuous</i> 1 < 2</p></div>").unwrap();
+    assert_eq!(hyphenated, "<p>an\u{ad}frac\u{ad}tu\u{ad}ous</i> 1 < 2</p></div>");
+    assert_eq!(html.hyphenate(&hyphenated).unwrap(), hyphenated);
+    assert_eq!(html.hyphenate("an\u{ad}fractuous").unwrap(), "an\u{ad}fractuous");
+
+    assert_eq!(html.hyphenate("<p title=\"x>hyphenation"), Err(Error::Tag(0)));
+    assert_eq!(html.hyphenate("hyphenation <!-- -- >"), Err(Error::Comment(12)));
+    assert_eq!(html.hyphenate("<p><script>hyphenation</p>"),
+               Err(Error::Element("script".to_owned(), 3)));
+
+    let unknown = html.default_language(None);
+    assert_eq!(unknown.hyphenate("<p>hyphenation</p>").unwrap(), "<p>hyphenation</p>");
+}
+
+#[test]
+fn discretionaries() {
+    use hyphenation::discretionary::{Discretionary, Latex, Plaintext, Typst};
+
+    let hyphenated = EN_US.hyphenate("hyphenation");
+    assert_eq!(hyphenated.render(&Latex), r"hy\-phen\-a\-tion");
+    assert_eq!(hyphenated.render(&Typst), "hy#sym.shy;phen#sym.shy;a#sym.shy;tion");
+    assert_eq!(hyphenated.render(&Plaintext::SoftHyphen),
+               "hy\u{ad}phen\u{ad}a\u{ad}tion");
+    assert_eq!(EN_US.hyphenate("bevies").render(&Latex), "bevies");
+
+    let hyphenated = HU.hyphenate("asszonnyal");
+    assert_eq!(hyphenated.discretionaries(),
+               [Discretionary { index :      2,
+                                span :       1 .. 2,
+                                pre_break :  "sz",
+                                post_break : "",
+                                no_break :   "s", },
+                Discretionary { index :      6,
+                                span :       5 .. 8,
+                                pre_break :  "ny",
+                                post_break : "ny",
+                                no_break :   "nny", }]);
+    assert_eq!(hyphenated.render(&Latex),
+               r"a\discretionary{sz-}{}{s}szo\discretionary{ny-}{ny}{nny}al");
+    assert_eq!(hyphenated.render(&Plaintext::ZeroWidthSpace),
+               "as\u{200b}szon\u{200b}nyal");
+
+    let ca = fiat_ext(Catalan);
+    let hyphenated = ca.hyphenate("col·lecció");
+    assert_eq!(hyphenated.render(&Latex), r"col\discretionary{-}{}{·}lec\-ció");
+
+    // Standard breaks are plain discretionaries.
+    let hyphenated = EN_US.hyphenate("anfractuous");
+    let d = &hyphenated.discretionaries()[0];
+    assert!(d.is_plain() && d.index == 2 && d.span == (2 .. 2));
+}
+
+#[test]
+fn weighted_breaks() {
+    use hyphenation::score::{Quality, Rank, Weigh};
+
+    fn qualities<B>(w : hyphenation::hyphenator::Word<hyphenation::score::Weighted<B>>)
+                    -> Vec<Quality> {
+        w.breaks.iter().map(|b| b.quality).collect()
+    }
+
+    // Weighted breaks are those of `hyphenate`, in the same order.
+    for w in &["hyphenation", "anfractuous", "MUCİLAGİNOUS", "an\u{ad}fractuous"] {
+        let weighted : Vec<_> =
+            EN_US.hyphenate_weighted(w).breaks.iter().map(|b| b.opportunity).collect();
+        assert_eq!(weighted, EN_US.hyphenate(w).breaks);
+    }
+
+    assert_eq!(qualities(EN_US.hyphenate_weighted("hyphenation")),
+               vec![Quality::Exception; 3]);
+    assert_eq!(qualities(EN_US.hyphenate_weighted("an\u{ad}fractuous")),
+               vec![Quality::SoftHyphen]);
+    assert_eq!(qualities(EN_US.hyphenate_weighted("MUCİLAGİNOUS")),
+               vec![Quality::Pattern(1), Quality::Pattern(1)]);
+
+    // The compound boundary of "Silben·trennung" outweighs its syllables.
+    let de = fiat_std(German1996);
+    let silbentrennung = de.hyphenate_weighted("Silbentrennung");
+    assert_eq!(qualities(silbentrennung.clone()),
+               vec![Quality::Pattern(1), Quality::Pattern(5), Quality::Pattern(3)]);
+    let preferred : Vec<_> = silbentrennung.breaks
+                                           .iter()
+                                           .filter(|b| b.quality.rank(5) == Rank::Preferred)
+                                           .map(|b| b.opportunity)
+                                           .collect();
+    assert_eq!(preferred, vec![6]);
+    assert!(Quality::Pattern(5) < Quality::Exception);
+    assert_eq!(Quality::Exception.value(), None);
+
+    // Extended breaks keep their substitutions.
+    let asszonnyal = HU.hyphenate_weighted("asszonnyal");
+    assert_eq!(qualities(asszonnyal.clone()), vec![Quality::Pattern(5); 2]);
+    assert_eq!(asszonnyal.discretionaries(),
+               HU.hyphenate("asszonnyal").discretionaries());
+}
+
+#[test]
+fn compound_words() {
+    use hyphenation::compound::{CompoundHyphenator, Stems};
+    use hyphenation::score::{Quality, Rank};
+
+    fn weighed<B : Copy>(w : hyphenation::hyphenator::Word<hyphenation::score::Weighted<B>>)
+                         -> Vec<(B, Quality)> {
+        w.breaks.iter().map(|b| (b.opportunity, b.quality)).collect()
+    }
+
+    // Minima apply to each component: "mother" is too short to break.
+    let compounds = CompoundHyphenator::new(&*EN_US);
+    assert_eq!(EN_US.hyphenate("mother-in-law").breaks, vec![4]);
+    assert_eq!(weighed(compounds.hyphenate("mother-in-law")), vec![]);
+    assert_eq!(compounds.components("input/output"),
+               vec![(0, "input"), (6, "output")]);
+    assert_eq!(weighed(compounds.hyphenate("input/output")),
+               vec![(2, Quality::Pattern(1)), (9, Quality::Pattern(3))]);
+    assert_eq!(compounds.components("-ish"), vec![(1, "ish")]);
+    // The number of breaks is limited over the whole word.
+    let single = EN_US.options.max_breaks(1);
+    assert_eq!(weighed(compounds.hyphenate_with("input/output", &single)),
+               vec![(9, Quality::Pattern(3))]);
+
+    // Closed compounds are divided into as few stems as possible.
+    let de = fiat_std(German1996);
+    let stems =
+        Stems::from_words(&de, vec!["Donau", "dampf", "schiff", "fahrt", "schifffahrt"]).unwrap();
+    assert_eq!(stems.joints("donaudampfschifffahrt"), vec![5, 10]);
+    assert_eq!(stems.joints("donaudampfer"), vec![]);
+
+    let compounds = CompoundHyphenator::new(&de).with_stems(stems);
+    assert_eq!(compounds.components("Donau-Dampfschiff"),
+               vec![(0, "Donau"), (6, "Dampf"), (11, "schiff")]);
+    assert_eq!(weighed(compounds.hyphenate("Donaudampfschifffahrt")),
+               vec![(2, Quality::Pattern(1)),
+                    (5, Quality::Joint),
+                    (10, Quality::Joint),
+                    (16, Quality::Pattern(1))]);
+    assert_eq!(weighed(compounds.hyphenate_with("Donaudampfschifffahrt",
+                                                &de.options.max_breaks(2))),
+               vec![(5, Quality::Joint), (10, Quality::Joint)]);
+    assert!(Quality::Joint > Quality::Pattern(9));
+    assert_eq!(Quality::Joint.rank(5), Rank::Preferred);
+
+    // Extended joints alter nothing.
+    let stems = Stems::from_words(&*HU, vec!["asszony", "nyal"]).unwrap();
+    let compounds = CompoundHyphenator::new(&*HU).with_stems(stems);
+    let hyphenated = compounds.hyphenate("asszonynyal");
+    let joint = hyphenated.breaks
+                          .iter()
+                          .find(|b| b.quality == Quality::Joint)
+                          .unwrap();
+    assert_eq!(joint.opportunity, (7, None));
+}
+
+#[test]
+fn pattern_tracing() {
+    use hyphenation::score::Score;
+    use hyphenation::trace::Explain;
+
+    let en_gb = fiat_std(EnglishGB);
+    let trace = en_gb.explain("Firkin");
+    assert_eq!(trace.word, "firkin");
+    assert_eq!(trace.values, en_gb.score("firkin"));
+    let patterns : Vec<_> = trace.matches.iter().map(|m| m.pattern()).collect();
+    assert_eq!(patterns, vec![".fi2", "2ir", "r2k", "r5kin.", "k1in", "k2in."]);
+    assert_eq!(trace.winners, vec![Some(1), Some(0), Some(3), Some(5), None]);
+    assert_eq!(trace.winner(3).unwrap().values(&trace.word), vec![(3, 5)]);
+    assert_eq!(trace.to_string(),
+               "«f|i|r|k|i|n»
+-------------
+«f i2
+  2i r
+     r2k
+     r5k i n»
+       k1i n
+       k2i n»
+-------------
+ f|i|r|k|i|n
+  2 2 5 2 0");
+
+    for w in &["anfractuous", "hyphenation", "mucilaginous", "quaintly"] {
+        assert_eq!(EN_US.explain(w).values, EN_US.score(w));
+    }
+
+    let trace = HU.explain("asszonnyal");
+    let values : Vec<_> = HU.score("asszonnyal").iter().map(|&(v, _)| v).collect();
+    assert_eq!(trace.values, values);
+    let winner = trace.winner(2).unwrap();
+    assert_eq!(winner.pattern(), "as5szonn");
+    assert_eq!(winner.subregion.map(|(_, r)| r.substitution.as_str()), Some("sz"));
+}
+
+#[test]
+fn language_tags() {
+    use hyphenation::tag::{Error, Tag};
+
+    let tag : Tag = "SR_cyrl-rs-x-Foo".parse().unwrap();
+    assert_eq!(tag.language, "sr");
+    assert_eq!(tag.script.as_deref(), Some("Cyrl"));
+    assert_eq!(tag.region.as_deref(), Some("RS"));
+    assert_eq!(tag.private_use, vec!["foo"]);
+    assert_eq!(tag.to_string(), "sr-Cyrl-RS-x-foo");
+    assert_eq!(Tag::parse("de-CH-1901-u-co-phonebk").unwrap().to_string(),
+               "de-CH-1901-u-co-phonebk");
+
+    // Every language code is a well-formed tag, resolved to its own language.
+    for code in &["af", "de-ch-1901", "el-polyton", "fi-x-school", "mul-ethi", "zh-latn-pinyin"] {
+        let resolved = Language::from_tag(code).unwrap();
+        assert_eq!(resolved.language.code(), *code);
+        assert!(resolved.ignored.is_empty());
+    }
+    assert_eq!(Language::try_from_code("EN_us"), Some(EnglishUS));
+    assert_eq!(Language::try_from_code("en"), None);
+
+    let resolve = |tag| Language::from_tag(tag).map(|r| (r.language, r.ignored));
+    assert_eq!(resolve("en-US"), Ok((EnglishUS, vec![])));
+    assert_eq!(resolve("en-AU"), Ok((EnglishUS, vec!["AU".to_owned()])));
+    assert_eq!(resolve("de-DE-1901"), Ok((German1901, vec!["DE".to_owned()])));
+    assert_eq!(resolve("de-CH"), Ok((GermanSwiss, vec![])));
+    assert_eq!(resolve("de-CH-1996"), Ok((German1996, vec!["CH".to_owned()])));
+    assert_eq!(resolve("sr-Cyrl-RS"), Ok((SerbianCyrillic, vec!["RS".to_owned()])));
+    assert_eq!(resolve("sr"), Ok((SerbianCyrillic, vec![])));
+    assert_eq!(resolve("sh"), Ok((SerbocroatianLatin, vec![])));
+    assert_eq!(resolve("el"), Ok((GreekMono, vec![])));
+    assert_eq!(resolve("la"), Ok((Latin, vec![])));
+    assert_eq!(resolve("la-x-classic"), Ok((LatinClassic, vec![])));
+    assert_eq!(resolve("am-ET"), Ok((Ethiopic, vec!["ET".to_owned()])));
+    assert_eq!(resolve("fr-u-co-phonebk"), Ok((French, vec!["u-co-phonebk".to_owned()])));
+
+    assert_eq!(resolve(""), Err(Error::Empty));
+    assert_eq!(resolve("en--us"), Err(Error::Malformed("".to_owned())));
+    assert_eq!(resolve("en-US-toolongsubtag"), Err(Error::Malformed("toolongsubtag".to_owned())));
+    assert_eq!(resolve("de-1996-DE"), Err(Error::Malformed("de".to_owned())));
+    assert_eq!(resolve("tlh"), Err(Error::Language("tlh".to_owned())));
+    assert_eq!(resolve("zh-Hans"),
+               Err(Error::Variety { tag :       "zh-Hans".to_owned(),
+                                    supported : vec![Chinese], }));
+    assert_eq!(Error::Variety { tag :       "zh-Hans".to_owned(),
+                                supported : vec![Chinese], }.to_string(),
+               "`zh-Hans` matches no supported variety of its language (zh-latn-pinyin)");
+}
+
+#[test]
+fn language_metadata() {
+    use hyphenation::load::{Header, Kind};
+    use std::collections::HashSet;
+
+    let languages : Vec<_> = Language::all().collect();
+    assert_eq!(languages.len(), 80);
+    assert_eq!(languages.iter().map(|l| l.code()).collect::<HashSet<_>>().len(), 80);
+    for &l in &languages {
+        assert_eq!(Language::try_from_code(l.code()), Some(l));
+        assert!(!l.scripts().is_empty() && l.scripts().iter().all(|s| s.len() == 4));
+        assert!(!l.english_name().is_empty() && !l.native_name().is_empty());
+    }
+
+    assert_eq!(SerbianCyrillic.scripts(), ["Cyrl"]);
+    assert_eq!(Sanskrit.scripts()[0], "Deva");
+    assert_eq!(NorwegianBokmal.english_name(), "Norwegian Bokmål");
+    assert_eq!(German1996.native_name(), "Deutsch (reformierte Rechtschreibung)");
+
+    // Dictionary headers record the version of their patterns.
+    for (lang, kind, suffix) in &[(EnglishUS, Kind::Standard, "standard"),
+                                  (Hungarian, Kind::Extended, "extended"),
+                                  (Catalan, Kind::Extended, "extended")]
+    {
+        let source = lang.patterns(*kind).unwrap();
+        let path = format!("dictionaries/{}.{}.bincode", lang.code(), suffix);
+        let (header, _) = Header::read(&std::fs::read(path).unwrap()).unwrap();
+        assert_eq!(header.source, source.version);
+    }
+
+    let en_us = EnglishUS.patterns(Kind::Standard).unwrap();
+    assert_eq!(en_us.package, "hyph-utf8");
+    assert!(en_us.url.ends_with("/hyph-en-us.tex"));
+    let hu = Hungarian.patterns(Kind::Extended).unwrap();
+    assert_eq!(hu.license, Some("MPL-1.1 OR GPL-2.0-or-later OR LGPL-2.1-or-later"));
+    assert_eq!(EnglishUS.patterns(Kind::Extended), None);
+
+    // Every bundled pattern set is attributed.
+    for lang in Language::all() {
+        for kind in &[Kind::Standard, Kind::Extended] {
+            if let Some(source) = lang.patterns(*kind) {
+                assert!(source.license.is_some_and(|l| !l.is_empty()), "{:?}", lang);
+                assert!(!source.authors.is_empty(), "{:?}", lang);
+            }
+        }
+    }
+}
+
+#[test]
+fn registry() {
+    use hyphenation::registry::{self, Error, Registry};
+    use std::sync::Arc;
 
+    let tags = ["en", "en_GB", "de-AT", "de-CH", "sr-Latn-RS", "sr-Cyrl", "no", "x-klingon"];
+    let resolved : Vec<_> = tags.iter().map(|t| registry::resolve(t).ok()).collect();
+    assert_eq!(resolved,
+               vec![Some(EnglishUS),
+                    Some(EnglishGB),
+                    Some(German1996),
+                    Some(GermanSwiss),
+                    Some(SerbocroatianLatin),
+                    Some(SerbianCyrillic),
+                    Some(NorwegianBokmal),
+                    None]);
+
+    let registry : Registry<Standard> = Registry::new().directory("dictionaries");
+    let en_us = registry.resolve("en").unwrap();
+    assert_eq!(en_us.language(), EnglishUS);
+    assert!(Arc::ptr_eq(&en_us, &registry.get(EnglishUS).unwrap()));
+    assert!(matches!(registry.resolve("x-klingon"), Err(Error::Tag(_))));
+
+    // Dictionaries are shared among threads, and loaded only once.
+    let registry = Arc::new(registry);
+    let loaded : Vec<_> = (0 .. 4).map(|_| {
+                                      let registry = Arc::clone(&registry);
+                                      std::thread::spawn(move || registry.get(Hungarian).unwrap())
+                                  })
+                                  .map(|t| t.join().unwrap())
+                                  .collect();
+    assert!(loaded.iter().all(|d| Arc::ptr_eq(d, &loaded[0])));
+    let mut languages = registry.loaded();
+    languages.sort_by_key(|l| l.code());
+    assert_eq!(languages, vec![EnglishUS, Hungarian]);
+
+    // Providers are consulted in order.
+    let registry : Registry<Extended> =
+        Registry::new().provider(|lang| match lang {
+                           Catalan => Some(Ok(fiat_ext(Catalan))),
+                           _ => None,
+                       })
+                       .directory("dictionaries");
+    assert_eq!(registry.resolve("ca-ES").unwrap().language(), Catalan);
+    assert_eq!(registry.get(Hungarian).unwrap().language(), Hungarian);
+    assert!(matches!(registry.get(EnglishUS), Err(Error::Unavailable(EnglishUS))));
+
+
+    let mistaken : Registry<Standard> =
+        Registry::new().provider(|lang| {
+                           Some(Standard::from_path(lang, "dictionaries/en-gb.standard.bincode"))
+                       });
+    assert!(matches!(mistaken.get(EnglishUS), Err(Error::Load(EnglishUS, _))));
+}
+
+#[test]
+fn text() {
     let hyphenate_text = |text : &str| -> String {
         text.split_word_bounds()
             .flat_map(|word| EN_US.hyphenate(word).into_iter())
@@ -174,6 +859,317 @@ fn text() {
     assert_eq!(seg1, expect1);
 }
 
+#[test]
+fn text_hyphenator() {
+    let t0 = "I know noble accents / And lucid, inescapable rhythms; […]";
+    let h0 = EN_US.hyphenate_text(t0);
+    let seg0 : String = h0.iter().collect();
+    assert_eq!(seg0, "I know no-ble ac-cents / And lu-cid, in-escapable rhythms; […]");
+
+    let t1 = "ever-burning sulphur unconsumed";
+    let h1 = EN_US.hyphenate_text(t1);
+    assert_eq!(h1.breaks, vec![9, 16, 23, 26]);
+
+    // Numbers are skipped, and apostrophes delimit words of their own.
+    let t2 = "anfractuous1 hyphenation's";
+    let h2 = EN_US.hyphenate_text(t2);
+    let seg2 : Vec<&str> = h2.iter().segments().collect();
+    assert_eq!(seg2, vec!["anfractuous1 hy", "phen", "a", "tion's"]);
+
+    let t3 = "Az asszonnyal, az esszé.";
+    let seg3 : String = HU.hyphenate_text(t3).into_iter().collect();
+    assert_eq!(seg3, "Az asz-szony-nyal, az esz-szé.");
+}
+
+#[test]
+fn text_collected_equals_original() {
+    fn property(original : String) -> bool {
+        let collected : String = EN_US.hyphenate_text(&original).iter().segments().collect();
+
+        collected == original
+    }
+
+    quickcheck(property as fn(String) -> bool);
+}
+
+#[test]
+fn knuth_plass() {
+    use hyphenation::linebreak::*;
+
+    let text = "Far out in the uncharted backwaters of the unfashionable end of the western \
+                spiral arm of the Galaxy lies a small unregarded yellow sun.";
+    let paragraph = Paragraph::new(text, &*EN_US, &Chars, Parameters::default());
+    let lines = paragraph.lines(&[30.0]);
+    let texts : Vec<&str> = lines.iter().map(|l| l.text.as_ref()).collect();
+    assert_eq!(texts,
+               vec!["Far out in the uncharted back-",
+                    "waters of the unfashionable end",
+                    "of the western spiral arm of",
+                    "the Galaxy lies a small unre-",
+                    "garded yellow sun."]);
+    assert!(lines.iter().all(|l| l.ratio >= -1.0));
+
+    let rejoined : String = lines.iter()
+                                 .map(|l| &text[l.range.clone()])
+                                 .collect::<Vec<_>>()
+                                 .join(" ");
+    assert_eq!(rejoined.replace("back waters", "backwaters")
+                       .replace("unre garded", "unregarded"),
+               text);
+
+    // Boxes wider than the line are set on overfull lines of their own.
+    let items = [Item::Box { width : 3.0 },
+                 Item::Glue { width :   1.0,
+                              stretch : 1.0,
+                              shrink :  0.0, },
+                 Item::Box { width : 12.0 }];
+    let items : Vec<_> = items.iter().cloned().chain(Item::finishing().iter().cloned()).collect();
+    let breaks = total_fit(&items, &[5.0], &Parameters::default());
+    let positions : Vec<_> = breaks.iter().map(|b| b.position).collect();
+    assert_eq!(positions, vec![1, 5]);
+    assert!(breaks[1].ratio < -1.0);
+}
+
+#[test]
+fn knuth_plass_extended() {
+    use hyphenation::linebreak::*;
+
+    let paragraph = Paragraph::new("Az asszonnyal", &*HU, &Chars, Parameters::default());
+    let texts : Vec<_> = paragraph.lines(&[6.0]).into_iter().map(|l| l.text).collect();
+    assert_eq!(texts, vec!["Az", "asz-", "szony-", "nyal"]);
+
+    // Unbroken, the word keeps its own letters and width.
+    let lines = paragraph.lines(&[8.0]);
+    let texts : Vec<_> = lines.iter().map(|l| l.text.as_ref()).collect();
+    assert_eq!(texts, vec!["Az asz-", "szonnyal"]);
+    assert_eq!(lines[1].ratio, 0.0);
+}
+
+#[test]
+fn first_fit() {
+    use hyphenation::linebreak::{first_fit, Chars};
+
+    let text = "Far out in the uncharted backwaters of the unfashionable end\n\nof ninety-two";
+    let lines = first_fit(text, &*EN_US, &Chars, 20.0, "-");
+    assert_eq!(lines,
+               vec!["Far out in the un-",
+                    "charted backwaters",
+                    "of the unfashionable",
+                    "end",
+                    "",
+                    "of ninety-two"]);
+
+    let lines = first_fit(text, &*EN_US, &|s : &str| s.len() as f64, 12.0, "\u{00ad}");
+    assert_eq!(lines[.. 3], ["Far out in", "the un\u{00ad}", "charted"]);
+
+    // Breaks dictated by `Extended` dictionaries alter the word accordingly.
+    let lines = first_fit("Az asszonnyal", &*HU, &Chars, 6.0, "-");
+    assert_eq!(lines, vec!["Az", "asz-", "szony-", "nyal"]);
+}
+
+#[test]
+fn tex_source() {
+    use hyphenation::parse::tex;
+
+    let patterns = std::fs::read_to_string("patterns/hyph-en-us.pat.txt").unwrap();
+    let exceptions = std::fs::read_to_string("patterns/hyph-en-us.hyp.txt").unwrap();
+    let source = format!("% Patterns for American English\n\
+                          \\message{{en-US hyphenation {{patterns}}}}\n\
+                          \\patterns{{%\n{}}}\n\
+                          \\hyphenation{{ % exceptions\n{}}}\n",
+                         patterns, exceptions);
+    let en_us = tex::standard(EnglishUS, &source).unwrap();
+    for w in &["anfractuous", "hyphenation", "bevies", "unconsumed", "CAPITAL"] {
+        assert_eq!(en_us.hyphenate(w).breaks, EN_US.hyphenate(w).breaks);
+    }
+
+    let escaped = "\\patterns{ 1^^e9 ^^^^00e8 1t % a comment }\n x1y }";
+    let parsed = tex::Source::parse(escaped).unwrap();
+    assert_eq!(parsed.patterns, vec!["1é", "è", "1t", "x1y"]);
+    assert!(tex::Source::parse("\\patterns{ a1b").is_err());
+}
+
+#[test]
+fn libhyphen_source() {
+    use hyphenation::parse::libhyphen;
+
+    let patterns = std::fs::read("patterns/hyph-hu.ext.txt").unwrap();
+    let dic = [b"UTF-8\nLEFTHYPHENMIN 2\nRIGHTHYPHENMIN 2\n% Hungarian\n" as &[u8], &patterns].concat();
+    let hu = libhyphen::extended(Hungarian, &dic).unwrap();
+    let v0 : Vec<_> = hu.hyphenate("asszonnyal").into_iter().segments().collect();
+    assert_eq!(v0, vec!["asz", "szony", "nyal"]);
+
+    // Declared minima take precedence over those of the language.
+    let dic = [b"UTF-8\nLEFTHYPHENMIN 4\n" as &[u8], &patterns].concat();
+    let hu = libhyphen::standard(Hungarian, &dic).unwrap();
+    assert_eq!(hu.options.minima, (4, 2));
+    let v1 : Vec<_> = hu.hyphenate("asszonnyal").into_iter().segments().collect();
+    assert_eq!(v1, vec!["asszon", "nyal"]);
+
+    // Legacy character sets.
+    let parsed = |dic : &[u8]| libhyphen::Source::parse(dic).map(|s| s.patterns);
+    assert_eq!(parsed(b"ISO8859-1\n\xe91t\n").unwrap(), vec!["é1t"]);
+    assert_eq!(parsed(b"ISO8859-2\n\xb31\xb1\n").unwrap(), vec!["ł1ą"]);
+    assert_eq!(parsed(b"KOI8-R\n\xc11\xc2\n").unwrap(), vec!["а1б"]);
+    assert_eq!(parsed(b"microsoft-cp1251\n\xe01\xe1\n").unwrap(), vec!["а1б"]);
+    assert_eq!(parsed(b"ISO8859-7\n\xe11\xe2\n").unwrap(), vec!["α1β"]);
+    assert!(matches!(parsed(b"ISO8859-7\n\xff1a\n"), Err(libhyphen::Error::Encoding)));
+    let unknown = parsed(b"EBCDIC\n1a\n").unwrap_err();
+    assert_eq!(unknown.to_string(), "unsupported character set `EBCDIC`");
+
+    // Of a two-level dictionary, the second level is kept, and the compound
+    // level skipped.
+    let header = "UTF-8\nLEFTHYPHENMIN 2\nRIGHTHYPHENMIN 2\nCOMPOUNDLEFTHYPHENMIN 2\n\
+                  COMPOUNDRIGHTHYPHENMIN 2\nNOHYPHEN -,'\n1-1\n1'1\nzo1n\nNEXTLEVEL\n";
+    let dic = [header.as_bytes(), &patterns].concat();
+    let source = libhyphen::Source::parse(&dic).unwrap();
+    let skipped : Vec<_> = source.skipped.iter().map(|(d, l)| (d.as_str(), *l)).collect();
+    assert_eq!(skipped,
+               vec![("COMPOUNDLEFTHYPHENMIN", 4),
+                    ("COMPOUNDRIGHTHYPHENMIN", 5),
+                    ("NOHYPHEN", 6),
+                    ("NEXTLEVEL", 10)]);
+    assert!(!source.patterns.iter().any(|p| p == "1-1" || p == "zo1n"));
+    let hu = source.extended(Hungarian, Normalization::None).unwrap();
+    assert_eq!(hu.options.minima, (2, 2));
+    let v2 : Vec<_> = hu.hyphenate("asszonnyal").into_iter().segments().collect();
+    assert_eq!(v2, vec!["asz", "szony", "nyal"]);
+
+    // A third level is not read.
+    assert_eq!(parsed(b"UTF-8\n1b\nNEXTLEVEL\n1c\nNEXTLEVEL\n1d\n").unwrap(), vec!["1c"]);
+    assert!(parsed(b"UTF-8\nLEFTHYPHENMIN two\n").is_err());
+}
+
+#[test]
+fn dictionary_builder() {
+    let patterns = BufReader::new(File::open("patterns/hyph-en-us.pat.txt").unwrap());
+    let exceptions = BufReader::new(File::open("patterns/hyph-en-us.hyp.txt").unwrap());
+    let en_us = DictionaryBuilder::new(EnglishUS).patterns_from_reader(patterns)
+                                                 .unwrap()
+                                                 .exceptions_from_reader(exceptions)
+                                                 .unwrap()
+                                                 .standard()
+                                                 .unwrap();
+    for w in &["hyphenation", "anfractuous", "anisotropic", "ribonuclease"] {
+        assert_eq!(en_us.hyphenate(w).breaks, EN_US.hyphenate(w).breaks);
+    }
+
+    // Built dictionaries can be stored and loaded again.
+    let mut buffer = vec![];
+    en_us.to_writer(&mut buffer).unwrap();
+    let loaded = Standard::from_reader(EnglishUS, &mut buffer.as_slice()).unwrap();
+    assert_eq!(loaded.hyphenate("hyphenation").breaks, vec![2, 6, 7]);
+
+    let hu = DictionaryBuilder::new(Hungarian).patterns(vec!["as5szon2y/sz=,2,1", ".a2"])
+                                              .exceptions(vec!["Nagy-szom-bat"])
+                                              .minima(1, 1)
+                                              .extended()
+                                              .unwrap();
+    assert_eq!(hu.options.minima, (1, 1));
+    let v : Vec<_> = hu.hyphenate("asszony").into_iter().segments().collect();
+    assert_eq!(v, vec!["asz", "szony"]);
+    let e : Vec<_> = hu.hyphenate("nagyszombat").into_iter().segments().collect();
+    assert_eq!(e, vec!["nagy", "szom", "bat"]);
+
+    // Options are carried into the dictionary, and minima set after them
+    // amend them.
+    let options = HyphenationOptions::new((3, 3)).min_length(6).max_breaks(2);
+    let builder = DictionaryBuilder::new(EnglishUS).patterns(vec!["hy3ph", "1na", "n2at"])
+                                                   .options(options);
+    assert_eq!(builder.standard().unwrap().options(), options);
+    assert_eq!(builder.extended().unwrap().options(), options);
+    let amended = builder.minima(1, 2).standard().unwrap();
+    assert_eq!(amended.options(), options.minima(1, 2));
+}
+
+#[test]
+fn malformed_patterns() {
+    use hyphenation::parse::{Parse, Problem};
+    use hyphenation_commons::dictionary::{extended, Patterns};
+
+    let e0 = Patterns::tally("ab12c").unwrap_err();
+    assert_eq!((e0.column, e0.problem), (4, Problem::Digit('2')));
+    let long = ["1", &"a".repeat(256), "2"].concat();
+    assert_eq!(Patterns::tally(&long).unwrap_err().problem, Problem::IndexOverflow(256));
+
+    let problem = |p| extended::Patterns::tally(p).unwrap_err().problem;
+    assert_eq!(problem("as5szon2y/sz,2,1"), Problem::MissingBreakpoint);
+    assert_eq!(problem("as5szon2y/sz="), Problem::MissingSeparator);
+    assert_eq!(problem("as5szon2y/sz=,2"), Problem::MissingSeparator);
+    assert_eq!(problem("as5szon2y/sz=,two,1"), Problem::Number("two".to_owned()));
+    assert_eq!(problem("as5szon2y/sz=,2,9"), Problem::SubregionOutOfRange);
+    assert_eq!(problem("as5szon2y/sz=,2,0"), Problem::SubregionOutOfRange);
+    assert_eq!(problem("as5szon2y/sz=,3,1"), Problem::Unmarked);
+
+    // Builders and parsers report where the pattern was found.
+    let err = DictionaryBuilder::new(Hungarian).patterns_from_reader(&b".a2\nas5szon2y/sz=,2,9"[..])
+                                               .unwrap()
+                                               .extended()
+                                               .unwrap_err();
+    assert_eq!(err.to_string(), "2:15: subregion out of range in `as5szon2y/sz=,2,9`");
+    let zero_span = DictionaryBuilder::new(Hungarian).patterns(vec!["as5szon2y/sz=,2,0"])
+                                                     .extended();
+    assert!(zero_span.is_err());
+
+    // Columns count from the start of the line.
+    let err = DictionaryBuilder::new(EnglishUS).patterns_from_reader(&b"a1b  c23d"[..])
+                                               .unwrap()
+                                               .standard()
+                                               .unwrap_err();
+    assert_eq!(err.to_string(), "1:8: unexpected digit `3` in `c23d`");
+
+    let tex = "% comment\n\\patterns{\n  a1b\n  c23d ^^e9x12\n}";
+    let err = hyphenation::parse::tex::Source::parse(tex).unwrap_err();
+    assert_eq!(err.to_string(), "4:5: unexpected digit `3` in `c23d`");
+    let tex = "\\patterns{\n  a1b ^^e9x12\n}";
+    let err = hyphenation::parse::tex::Source::parse(tex).unwrap_err();
+    assert_eq!(err.to_string(), "2:13: unexpected digit `2` in `éx12`");
+
+    let dic = b"UTF-8\n  a1b\n  c23d\n";
+    let err = hyphenation::parse::libhyphen::Source::parse(dic).unwrap_err();
+    assert_eq!(err.to_string(), "3:5: unexpected digit `3` in `c23d`");
+}
+
+#[test]
+fn pattern_generation() {
+    use hyphenation::parse::Parse;
+    use hyphenation::patgen::{self, Parameters};
+    use hyphenation_commons::dictionary::Patterns;
+
+    let list = std::fs::read_to_string("patterns/hyph-en-us.hyp.txt").unwrap();
+    let words : Vec<&str> = list.lines().collect();
+    let patterns = patgen::generate(&words, &Parameters::default());
+    assert!(patterns.iter().all(|p| Patterns::tally(p).is_ok()));
+
+    let trained = DictionaryBuilder::new(EnglishUS).patterns(patterns)
+                                                   .minima(1, 1)
+                                                   .standard()
+                                                   .unwrap();
+    let correct = words.iter()
+                       .filter(|w| {
+                           let word : String = w.chars().filter(|&c| c != '-').collect();
+                           trained.hyphenate(&word).iter().collect::<String>() == **w
+                       })
+                       .count();
+    assert!(correct * 100 >= words.len() * 95);
+
+    // Words are prepared as the dictionary prepares them: Turkish `I` folds to
+    // dotless `ı`, rather than lowercasing to `i`.
+    let words = ["KI-RIL-MIŞ", "KI-RIK", "SI-CAK", "KA-PI", "IS-LAK"];
+    let parameters = Parameters { folding : Turkish.folding(),
+                                  ..Parameters::default() };
+    let patterns = patgen::generate(words, &parameters);
+    assert!(patterns.iter().any(|p| p.contains('ı')));
+    let trained = DictionaryBuilder::new(Turkish).patterns(patterns)
+                                                 .minima(1, 1)
+                                                 .standard()
+                                                 .unwrap();
+    for w in &words {
+        let word : String = w.chars().filter(|&c| c != '-').collect();
+        assert_eq!(trained.hyphenate(&word).iter().collect::<String>(), *w);
+    }
+}
+
 #[test]
 fn bounded_exception() {
     let e = "anisotropic"; // an-iso-trop-ic, by exception